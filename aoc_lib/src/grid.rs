@@ -0,0 +1,294 @@
+//! Rectangular grids of cells, like the maps and schematics many puzzles are drawn as.
+//!
+//! Positions are `(x, y)`, with `x` going right and `y` going down from the top left corner.
+
+use anyhow::{bail, Context};
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell, as `(x, y)`
+pub type Pos = (usize, usize);
+
+/// Offsets of the neighbours sharing an edge: north, east, south and west
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all neighbours, clockwise from north
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+	(0, -1),
+	(1, -1),
+	(1, 0),
+	(1, 1),
+	(0, 1),
+	(-1, 1),
+	(-1, 0),
+	(-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+	cells: Vec<T>,
+	width: usize,
+	height: usize,
+}
+
+impl<T> Grid<T> {
+	pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+	where
+		T: Clone,
+	{
+		Grid {
+			cells: vec![cell; width * height],
+			width,
+			height,
+		}
+	}
+
+	/// Parses one row per line, with every byte converted to a cell. Lines must all have the
+	/// same length.
+	pub fn parse(input: &str) -> anyhow::Result<Grid<T>>
+	where
+		T: TryFrom<u8>,
+		T::Error: Into<anyhow::Error>,
+	{
+		let mut cells = Vec::with_capacity(input.len());
+		let mut width = None;
+		let mut height = 0;
+		for (y, line) in input.lines().enumerate() {
+			let line = line.as_bytes();
+			match width {
+				None => width = Some(line.len()),
+				Some(width) if width != line.len() => bail!(
+					"line {} is {} cells long, expected {}",
+					y + 1,
+					line.len(),
+					width
+				),
+				Some(_) => {}
+			}
+			for (x, &b) in line.iter().enumerate() {
+				let cell = T::try_from(b)
+					.map_err(Into::into)
+					.with_context(|| format!("invalid cell at line {} column {}", y + 1, x + 1))?;
+				cells.push(cell);
+			}
+			height += 1;
+		}
+
+		Ok(Grid {
+			cells,
+			width: width.unwrap_or(0),
+			height,
+		})
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	fn idx(&self, (x, y): Pos) -> Option<usize> {
+		(x < self.width && y < self.height).then(|| y * self.width + x)
+	}
+
+	pub fn get(&self, pos: Pos) -> Option<&T> {
+		self.idx(pos).map(|idx| &self.cells[idx])
+	}
+
+	pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+		self.idx(pos).map(|idx| &mut self.cells[idx])
+	}
+
+	/// The position `(dx, dy)` away from `pos`, if it's in the grid
+	pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+		let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+		self.idx(pos).map(|_| pos)
+	}
+
+	/// Neighbours of `pos` sharing an edge with it, in the order of [`DIRECTIONS_4`]
+	pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+		DIRECTIONS_4
+			.into_iter()
+			.filter_map(move |delta| self.offset(pos, delta))
+	}
+
+	/// Neighbours of `pos` including diagonal ones, in the order of [`DIRECTIONS_8`]
+	pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+		DIRECTIONS_8
+			.into_iter()
+			.filter_map(move |delta| self.offset(pos, delta))
+	}
+
+	/// Cells with their positions, row by row
+	pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+		let width = self.width;
+		self.cells
+			.iter()
+			.enumerate()
+			.map(move |(idx, cell)| ((idx % width, idx / width), cell))
+	}
+
+	pub fn row(&self, y: usize) -> &[T] {
+		&self.cells[y * self.width..(y + 1) * self.width]
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+		// Not chunks_exact, which panics on a zero width
+		(0..self.height).map(move |y| self.row(y))
+	}
+
+	pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+		assert!(x < self.width, "column {} of a grid {} wide", x, self.width);
+		self.cells.iter().skip(x).step_by(self.width)
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+		(0..self.width).map(move |x| self.column(x))
+	}
+
+	/// Position of the first cell, row by row, matching `predicate`
+	pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+		self.iter()
+			.find(|(_, cell)| predicate(cell))
+			.map(|(pos, _)| pos)
+	}
+
+	/// Builds a `width` × `height` grid with the cell of every position taken from `self` at
+	/// `source(pos)`
+	fn remap(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Grid<T>
+	where
+		T: Clone,
+	{
+		let cells = (0..height)
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(|pos| self[source(pos)].clone())
+			.collect();
+		Grid {
+			cells,
+			width,
+			height,
+		}
+	}
+
+	/// Mirrors the grid along its main diagonal, turning rows into columns
+	pub fn transpose(&self) -> Grid<T>
+	where
+		T: Clone,
+	{
+		self.remap(self.height, self.width, |(x, y)| (y, x))
+	}
+
+	pub fn rotate_clockwise(&self) -> Grid<T>
+	where
+		T: Clone,
+	{
+		let height = self.height;
+		self.remap(self.height, self.width, |(x, y)| (y, height - 1 - x))
+	}
+
+	pub fn rotate_counterclockwise(&self) -> Grid<T>
+	where
+		T: Clone,
+	{
+		let width = self.width;
+		self.remap(self.height, self.width, |(x, y)| (width - 1 - y, x))
+	}
+}
+
+impl<T> Index<Pos> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, pos: Pos) -> &T {
+		match self.get(pos) {
+			Some(cell) => cell,
+			None => panic!(
+				"{:?} is outside of a {}x{} grid",
+				pos, self.width, self.height
+			),
+		}
+	}
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+	fn index_mut(&mut self, pos: Pos) -> &mut T {
+		let (width, height) = (self.width, self.height);
+		match self.get_mut(pos) {
+			Some(cell) => cell,
+			None => panic!("{:?} is outside of a {}x{} grid", pos, width, height),
+		}
+	}
+}
+
+/// Draws the grid as it was parsed, one line per row
+impl<T: Copy + Into<char>> Display for Grid<T> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		for row in self.rows() {
+			let line: String = row.iter().map(|&cell| cell.into()).collect();
+			writeln!(f, "{}", line)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Grid;
+
+	const GRID: &str = "ab.\n.#c\n";
+
+	#[test]
+	fn parse() {
+		let grid: Grid<u8> = Grid::parse(GRID).unwrap();
+		assert_eq!((grid.width(), grid.height()), (3, 2));
+		assert_eq!(grid[(2, 1)], b'c');
+		assert_eq!(grid.get((3, 0)), None);
+		assert_eq!(grid.to_string(), GRID);
+
+		assert!(Grid::<u8>::parse("ab\nc\n").is_err());
+		let empty: Grid<u8> = Grid::parse("").unwrap();
+		assert_eq!(
+			(empty.width(), empty.height(), empty.rows().count()),
+			(0, 0, 0)
+		);
+	}
+
+	#[test]
+	fn neighbours() {
+		let grid: Grid<u8> = Grid::parse(GRID).unwrap();
+		assert_eq!(
+			grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+			[(1, 0), (0, 1)]
+		);
+		assert_eq!(
+			grid.neighbours_8((1, 1)).collect::<Vec<_>>(),
+			[(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]
+		);
+		assert_eq!(grid.offset((2, 1), (-2, -1)), Some((0, 0)));
+		assert_eq!(grid.offset((2, 1), (1, 0)), None);
+	}
+
+	#[test]
+	fn rows_and_columns() {
+		let grid: Grid<u8> = Grid::parse(GRID).unwrap();
+		assert_eq!(grid.rows().collect::<Vec<_>>(), [b"ab.", b".#c"]);
+		assert_eq!(
+			grid.columns()
+				.map(|column| column.copied().collect::<Vec<_>>())
+				.collect::<Vec<_>>(),
+			[b"a.", b"b#", b".c"]
+		);
+		assert_eq!(grid.find(|&cell| cell == b'#'), Some((1, 1)));
+		assert_eq!(grid.find(|&cell| cell == b'x'), None);
+	}
+
+	#[test]
+	fn transpose_and_rotate() {
+		let grid: Grid<u8> = Grid::parse(GRID).unwrap();
+		assert_eq!(grid.transpose().to_string(), "a.\nb#\n.c\n");
+		assert_eq!(grid.rotate_clockwise().to_string(), ".a\n#b\nc.\n");
+		assert_eq!(grid.rotate_counterclockwise().to_string(), ".c\nb#\na.\n");
+		assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+	}
+}
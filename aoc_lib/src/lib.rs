@@ -0,0 +1,251 @@
+//! Code shared by the day crates.
+//!
+//! Every day implements [`Solution`] and gets its `main` from [`main!`].
+
+pub mod grid;
+pub mod math;
+pub mod ranges;
+
+pub use grid::Grid;
+pub use ranges::{RangeMap, RangeSet};
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+pub trait Solution {
+	/// Input shared by both parts, which may borrow from the puzzle input
+	type Parsed<'a>;
+	type Answer: Display;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>>;
+	fn part_1(parsed: &Self::Parsed<'_>) -> anyhow::Result<Self::Answer>;
+	fn part_2(parsed: &Self::Parsed<'_>) -> anyhow::Result<Self::Answer>;
+
+	/// Handles a day-specific `--option`, run instead of the parts when given. Returns `false`
+	/// for options the day doesn't have.
+	fn run_option(_option: &str, _parsed: &Self::Parsed<'_>) -> anyhow::Result<bool> {
+		Ok(false)
+	}
+
+	/// Parses `input` and solves part 1, mostly for tests
+	fn solve_part_1(input: &str) -> anyhow::Result<Self::Answer> {
+		Self::part_1(&Self::parse(input)?)
+	}
+
+	/// Parses `input` and solves part 2, mostly for tests
+	fn solve_part_2(input: &str) -> anyhow::Result<Self::Answer> {
+		Self::part_2(&Self::parse(input)?)
+	}
+}
+
+/// Defines `main` running the given [`Solution`] on the `input.txt` next to the crate's
+/// `Cargo.toml`, wherever the day is run from
+#[macro_export]
+macro_rules! main {
+	($solution:ty) => {
+		fn main() -> ::std::process::ExitCode {
+			$crate::run::<$solution>(env!("CARGO_MANIFEST_DIR"))
+		}
+	};
+}
+
+const USAGE: &str = "Usage: day_N [--part=1|2] [--input=PATH] [day-specific --options]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+	part: Option<u32>,
+	input: Option<PathBuf>,
+	help: bool,
+	/// Options for [`Solution::run_option`]
+	options: Vec<String>,
+}
+
+impl Args {
+	fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+		let mut parsed = Args {
+			part: None,
+			input: None,
+			help: false,
+			options: Vec::new(),
+		};
+
+		for arg in args {
+			if let Some(part) = arg.strip_prefix("--part=") {
+				parsed.part = match part.parse::<u32>() {
+					Ok(part @ (1 | 2)) => Some(part),
+					_ => return Err(format!("invalid part \"{}\", expected 1 or 2", part)),
+				};
+			} else if let Some(input) = arg.strip_prefix("--input=") {
+				parsed.input = Some(PathBuf::from(input));
+			} else if arg == "--help" || arg == "-h" {
+				parsed.help = true;
+			} else if arg.starts_with("--") {
+				parsed.options.push(arg);
+			} else {
+				return Err(format!("unexpected argument \"{}\"", arg));
+			}
+		}
+
+		Ok(parsed)
+	}
+}
+
+type PartFn<S> =
+	for<'a, 'b> fn(&'b <S as Solution>::Parsed<'a>) -> anyhow::Result<<S as Solution>::Answer>;
+
+/// Runs `S` with the command line arguments, printing `Part N: answer` lines to stdout and
+/// timings and errors to stderr
+pub fn run<S: Solution>(crate_dir: &str) -> ExitCode {
+	let args = match Args::parse(std::env::args().skip(1)) {
+		Ok(args) => args,
+		Err(err) => {
+			eprintln!("Error: {}\n{}", err, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+	if args.help {
+		println!("{}", USAGE);
+		return ExitCode::SUCCESS;
+	}
+
+	let input_path = args
+		.input
+		.unwrap_or_else(|| PathBuf::from(crate_dir).join("input.txt"));
+	let input = match std::fs::read_to_string(&input_path) {
+		Ok(input) => input,
+		Err(err) => {
+			eprintln!("Error: failed to read {}: {}", input_path.display(), err);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	let start = Instant::now();
+	let parsed = match S::parse(&input) {
+		Ok(parsed) => parsed,
+		Err(err) => {
+			eprintln!("Error: failed to parse {}: {:?}", input_path.display(), err);
+			return ExitCode::FAILURE;
+		}
+	};
+	eprintln!("Parsed in {}", format_duration(start.elapsed()));
+
+	if !args.options.is_empty() {
+		for option in &args.options {
+			match S::run_option(option, &parsed) {
+				Ok(true) => (),
+				Ok(false) => {
+					eprintln!("Error: unexpected argument \"{}\"\n{}", option, USAGE);
+					return ExitCode::FAILURE;
+				}
+				Err(err) => {
+					eprintln!("Error in {}: {:?}", option, err);
+					return ExitCode::FAILURE;
+				}
+			}
+		}
+		return ExitCode::SUCCESS;
+	}
+
+	let parts: [(u32, PartFn<S>); 2] = [(1, S::part_1), (2, S::part_2)];
+	let mut exit_code = ExitCode::SUCCESS;
+	for (part, solve) in parts {
+		if args.part.is_some_and(|p| p != part) {
+			continue;
+		}
+
+		let start = Instant::now();
+		let answer = solve(&parsed);
+		let time = start.elapsed();
+		match answer {
+			Ok(answer) => {
+				println!("Part {}: {}", part, answer);
+				eprintln!("Part {} solved in {}", part, format_duration(time));
+			}
+			Err(err) => {
+				eprintln!("Error in part {}: {:?}", part, err);
+				exit_code = ExitCode::FAILURE;
+			}
+		}
+	}
+
+	exit_code
+}
+
+/// Short human-readable form of `duration`, like `250µs`, `2.5ms` or `31.25s`
+pub fn format_duration(duration: Duration) -> String {
+	if duration < Duration::from_millis(1) {
+		format!("{}µs", duration.as_micros())
+	} else if duration < Duration::from_secs(1) {
+		format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+	} else {
+		format!("{:.2}s", duration.as_secs_f64())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Args, Solution};
+	use std::path::PathBuf;
+	use std::time::Duration;
+
+	struct Lines;
+
+	impl Solution for Lines {
+		type Parsed<'a> = Vec<&'a str>;
+		type Answer = usize;
+
+		fn parse(input: &str) -> anyhow::Result<Vec<&str>> {
+			Ok(input.lines().collect())
+		}
+
+		fn part_1(lines: &Vec<&str>) -> anyhow::Result<usize> {
+			Ok(lines.len())
+		}
+
+		fn part_2(lines: &Vec<&str>) -> anyhow::Result<usize> {
+			Ok(lines.iter().map(|line| line.len()).sum())
+		}
+	}
+
+	#[test]
+	fn solve_parts() {
+		assert_eq!(Lines::solve_part_1("ab\ncde\n").unwrap(), 2);
+		assert_eq!(Lines::solve_part_2("ab\ncde\n").unwrap(), 5);
+	}
+
+	#[test]
+	fn parse_args() {
+		let args = |args: &[&str]| Args::parse(args.iter().map(|arg| arg.to_string()));
+
+		assert_eq!(
+			args(&["--part=2", "--input=day_1/example.txt"]).unwrap(),
+			Args {
+				part: Some(2),
+				input: Some(PathBuf::from("day_1/example.txt")),
+				help: false,
+				options: Vec::new(),
+			}
+		);
+		assert_eq!(
+			args(&["--seed-for=46"]).unwrap().options,
+			[String::from("--seed-for=46")]
+		);
+		assert!(args(&["--part=3"]).is_err());
+		assert!(args(&["input.txt"]).is_err());
+	}
+
+	#[test]
+	fn format_duration() {
+		assert_eq!(super::format_duration(Duration::from_micros(250)), "250µs");
+		assert_eq!(
+			super::format_duration(Duration::from_micros(2_540)),
+			"2.5ms"
+		);
+		assert_eq!(
+			super::format_duration(Duration::from_millis(31_250)),
+			"31.25s"
+		);
+	}
+}
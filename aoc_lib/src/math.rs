@@ -0,0 +1,334 @@
+//! Number theory for cycles and congruences: checked gcd and lcm, extended Euclid, modular
+//! inverses and the Chinese Remainder Theorem for moduli that don't have to be coprime.
+//!
+//! Nothing here overflows silently. The results are `None` or [`CrtError::Overflow`] instead, and
+//! with the `bigint` feature [`big`] computes them with arbitrary precision.
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::{Div, Neg, Rem};
+
+/// Primitive integers the functions work with
+pub trait Integer: Copy + Ord + Debug + Div<Output = Self> + Rem<Output = Self> {
+	const ZERO: Self;
+	const ONE: Self;
+
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+	fn checked_sub(self, rhs: Self) -> Option<Self>;
+	fn checked_mul(self, rhs: Self) -> Option<Self>;
+	/// `None` for the minimum of signed integers, which has no positive counterpart
+	fn checked_abs(self) -> Option<Self>;
+	fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+/// Integers with negative numbers, needed for Bézout coefficients
+pub trait SignedInteger: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+	($($int:ty),* ; abs: $abs:expr) => {
+		$(
+			impl Integer for $int {
+				const ZERO: Self = 0;
+				const ONE: Self = 1;
+
+				fn checked_add(self, rhs: Self) -> Option<Self> {
+					<$int>::checked_add(self, rhs)
+				}
+
+				fn checked_sub(self, rhs: Self) -> Option<Self> {
+					<$int>::checked_sub(self, rhs)
+				}
+
+				fn checked_mul(self, rhs: Self) -> Option<Self> {
+					<$int>::checked_mul(self, rhs)
+				}
+
+				fn checked_abs(self) -> Option<Self> {
+					let abs: fn($int) -> Option<$int> = $abs;
+					abs(self)
+				}
+
+				fn rem_euclid(self, rhs: Self) -> Self {
+					<$int>::rem_euclid(self, rhs)
+				}
+			}
+		)*
+	};
+}
+
+impl_integer!(i64, i128; abs: |n| n.checked_abs());
+impl_integer!(u64, usize; abs: Some);
+
+impl SignedInteger for i64 {}
+impl SignedInteger for i128 {}
+
+/// Greatest common divisor, never negative. `None` only when it doesn't fit, like the gcd of
+/// `i64::MIN` and 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+	let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+	while b != T::ZERO {
+		(a, b) = (b, a % b);
+	}
+	Some(a)
+}
+
+/// Least common multiple, never negative, or `None` if it overflows
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+	if a == T::ZERO || b == T::ZERO {
+		return Some(T::ZERO);
+	}
+	let (a, b) = (a.checked_abs()?, b.checked_abs()?);
+	(a / gcd(a, b)?).checked_mul(b)
+}
+
+/// Least common multiple of all `values`, 1 without any
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+	values
+		.into_iter()
+		.try_fold(T::ONE, |acc, value| lcm(acc, value))
+}
+
+/// Returns `(g, x, y)` with `g` the gcd of `a` and `b` and `a * x + b * y == g`
+pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> Option<(T, T, T)> {
+	let (mut old_r, mut r) = (a, b);
+	let (mut old_x, mut x) = (T::ONE, T::ZERO);
+	let (mut old_y, mut y) = (T::ZERO, T::ONE);
+	while r != T::ZERO {
+		let q = old_r / r;
+		(old_r, r) = (r, old_r.checked_sub(q.checked_mul(r)?)?);
+		(old_x, x) = (x, old_x.checked_sub(q.checked_mul(x)?)?);
+		(old_y, y) = (y, old_y.checked_sub(q.checked_mul(y)?)?);
+	}
+	if old_r < T::ZERO {
+		Some((old_r.checked_abs()?, -old_x, -old_y))
+	} else {
+		Some((old_r, old_x, old_y))
+	}
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime
+pub fn mod_inverse<T: SignedInteger>(a: T, modulus: T) -> Option<T> {
+	if modulus <= T::ZERO {
+		return None;
+	}
+	let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus)?;
+	(g == T::ONE).then(|| x.rem_euclid(modulus))
+}
+
+/// `a * b % modulus` for `a` and `b` in `0..modulus`, even when the product overflows, as long as
+/// twice the modulus fits
+fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> Option<T> {
+	if let Some(product) = a.checked_mul(b) {
+		return Some(product % modulus);
+	}
+	let two = T::ONE.checked_add(T::ONE)?;
+	let (mut a, mut b, mut out) = (a, b, T::ZERO);
+	while b > T::ZERO {
+		if b % two == T::ONE {
+			out = out.checked_add(a)? % modulus;
+		}
+		a = a.checked_add(a)? % modulus;
+		b = b / two;
+	}
+	Some(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+	/// The congruences contradict each other
+	NoSolution,
+	Overflow,
+	/// A modulus isn't positive
+	InvalidModulus,
+}
+
+impl Display for CrtError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			CrtError::NoSolution => write!(f, "the congruences have no common solution"),
+			CrtError::Overflow => write!(f, "the solution overflows"),
+			CrtError::InvalidModulus => write!(f, "moduli must be positive"),
+		}
+	}
+}
+
+impl std::error::Error for CrtError {}
+
+/// Solves `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs, returning the smallest
+/// non-negative `x` and the lcm of the moduli, the period of all solutions. The moduli don't have
+/// to be coprime.
+pub fn crt<T: SignedInteger>(
+	congruences: impl IntoIterator<Item = (T, T)>,
+) -> Result<(T, T), CrtError> {
+	let (mut x, mut period) = (T::ZERO, T::ONE);
+	for (residue, modulus) in congruences {
+		if modulus <= T::ZERO {
+			return Err(CrtError::InvalidModulus);
+		}
+		let residue = residue.rem_euclid(modulus);
+
+		let g = gcd(period, modulus).ok_or(CrtError::Overflow)?;
+		// Both are non-negative, so the difference fits
+		let diff = residue.checked_sub(x).ok_or(CrtError::Overflow)?;
+		if diff % g != T::ZERO {
+			return Err(CrtError::NoSolution);
+		}
+
+		// x + period * k ≡ residue (mod modulus), so k ≡ diff / g * (period / g)⁻¹ (mod modulus / g)
+		let step_modulus = modulus / g;
+		let inverse = mod_inverse(period / g, step_modulus).ok_or(CrtError::Overflow)?;
+		let k = mul_mod((diff / g).rem_euclid(step_modulus), inverse, step_modulus)
+			.ok_or(CrtError::Overflow)?;
+
+		let new_period = period.checked_mul(step_modulus).ok_or(CrtError::Overflow)?;
+		// Below new_period, so it fits
+		x = period
+			.checked_mul(k)
+			.and_then(|step| x.checked_add(step))
+			.ok_or(CrtError::Overflow)?;
+		period = new_period;
+	}
+	Ok((x, period))
+}
+
+/// Arbitrary-precision versions of [`lcm_all`] and [`crt`], for results too large for `i128`
+#[cfg(feature = "bigint")]
+pub mod big {
+	use super::CrtError;
+	use num_bigint::BigInt;
+	use num_integer::Integer as _;
+
+	pub fn lcm_all(values: impl IntoIterator<Item = impl Into<BigInt>>) -> BigInt {
+		values
+			.into_iter()
+			.fold(BigInt::from(1), |acc, value| acc.lcm(&value.into()))
+	}
+
+	pub fn crt(
+		congruences: impl IntoIterator<Item = (impl Into<BigInt>, impl Into<BigInt>)>,
+	) -> Result<(BigInt, BigInt), CrtError> {
+		let zero = BigInt::from(0);
+		let (mut x, mut period) = (BigInt::from(0), BigInt::from(1));
+		for (residue, modulus) in congruences {
+			let (residue, modulus): (BigInt, BigInt) = (residue.into(), modulus.into());
+			if modulus <= zero {
+				return Err(CrtError::InvalidModulus);
+			}
+			let residue = residue.mod_floor(&modulus);
+
+			let ext = period.extended_gcd(&modulus);
+			let diff = residue - &x;
+			if !diff.is_multiple_of(&ext.gcd) {
+				return Err(CrtError::NoSolution);
+			}
+			let step_modulus = &modulus / &ext.gcd;
+			// ext.x is the inverse of period / gcd modulo step_modulus
+			let k = (diff / &ext.gcd * ext.x).mod_floor(&step_modulus);
+			x += &period * k;
+			period *= step_modulus;
+		}
+		Ok((x, period))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::CrtError;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	#[test]
+	fn gcd_and_lcm() {
+		assert_eq!(super::gcd(12i64, -18), Some(6));
+		assert_eq!(super::gcd(0u64, 7), Some(7));
+		assert_eq!(super::gcd(i64::MIN, 0), None);
+		assert_eq!(super::lcm(4usize, 6), Some(12));
+		assert_eq!(super::lcm(-4i64, 6), Some(12));
+		assert_eq!(super::lcm(0i64, 6), Some(0));
+
+		// The product of the two overflows, the lcm doesn't
+		let big = 3 * (1i64 << 60);
+		assert_eq!(super::lcm(big, 1 << 61), Some(3 << 61));
+		assert_eq!(super::lcm(i64::MAX, i64::MAX - 1), None);
+		assert_eq!(
+			super::lcm(i64::MAX as i128, i64::MAX as i128 - 1),
+			Some(i64::MAX as i128 * (i64::MAX as i128 - 1))
+		);
+		assert_eq!(super::lcm_all([2u64, 3, 4]), Some(12));
+		assert_eq!(super::lcm_all(Vec::<u64>::new()), Some(1));
+	}
+
+	#[test]
+	fn extended_gcd_and_inverse() {
+		assert_eq!(super::extended_gcd(240i64, 46), Some((2, -9, 47)));
+		assert_eq!(super::extended_gcd(-240i64, 46), Some((2, 9, 47)));
+		assert_eq!(super::mod_inverse(3i64, 11), Some(4));
+		assert_eq!(super::mod_inverse(-3i64, 11), Some(7));
+		assert_eq!(super::mod_inverse(4i64, 10), None);
+	}
+
+	#[test]
+	fn crt() {
+		assert_eq!(super::crt([(2i64, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+		// Not coprime
+		assert_eq!(super::crt([(2i64, 6), (8, 10)]), Ok((8, 30)));
+		assert_eq!(super::crt([(1i64, 6), (2, 10)]), Err(CrtError::NoSolution));
+		assert_eq!(super::crt([(1i64, 0)]), Err(CrtError::InvalidModulus));
+		assert_eq!(super::crt(Vec::<(i64, i64)>::new()), Ok((0, 1)));
+
+		// Products overflow on the way to a result that fits
+		let m = (1i64 << 62) - 57;
+		assert_eq!(super::crt([(1, 2), (m - 2, m)]), Ok((m - 2, 2 * m)));
+
+		// Moduli whose lcm only fits in an i128
+		let (a, b) = ((1i64 << 61) - 1, (1 << 31) - 1);
+		assert_eq!(
+			super::crt([(5, a), (7, b)]).map(|(_, period)| period),
+			Err(CrtError::Overflow)
+		);
+		assert_eq!(
+			super::crt([(5i128, a as i128), (7, b as i128)])
+				.map(|(x, _)| (x % a as i128, x % b as i128)),
+			Ok((5, 7))
+		);
+	}
+
+	#[cfg(feature = "bigint")]
+	#[test]
+	fn big() {
+		use num_bigint::BigInt;
+
+		let (a, b) = ((1i64 << 61) - 1, (1i64 << 31) - 1);
+		let (x, period) = super::big::crt([(5, a), (7, b)]).unwrap();
+		assert_eq!(period, BigInt::from(a) * BigInt::from(b));
+		assert_eq!((&x % a, &x % b), (BigInt::from(5), BigInt::from(7)));
+		assert_eq!(
+			super::big::crt([(1, 6), (2, 10)]),
+			Err(CrtError::NoSolution)
+		);
+		assert_eq!(super::big::lcm_all([4, 6, 10]), BigInt::from(60));
+	}
+
+	proptest! {
+		#[test]
+		fn crt_matches_brute_force(congruences in vec((0i64..50, 1i64..13), 0..4)) {
+			let period = super::lcm_all(congruences.iter().map(|(_, modulus)| *modulus)).unwrap();
+			let brute_force = (0..period)
+				.find(|x| congruences.iter().all(|(residue, modulus)| x % modulus == residue % modulus));
+
+			match super::crt(congruences.iter().copied()) {
+				Ok(solution) => prop_assert_eq!(Some(solution), brute_force.map(|x| (x, period))),
+				Err(err) => {
+					prop_assert_eq!(err, CrtError::NoSolution);
+					prop_assert_eq!(brute_force, None);
+				}
+			}
+		}
+
+		#[test]
+		fn lcm_is_a_common_multiple(a in 1u64..10_000, b in 1u64..10_000) {
+			let lcm = super::lcm(a, b).unwrap();
+			prop_assert!(lcm % a == 0 && lcm % b == 0);
+			prop_assert_eq!(lcm * super::gcd(a, b).unwrap(), a * b);
+		}
+	}
+}
@@ -0,0 +1,412 @@
+//! Sets of integers stored as ranges, for puzzles whose inputs are too large to handle point by
+//! point.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+/// Sorted, disjoint and non-adjacent half-open ranges
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+	ranges: Vec<Range<i64>>,
+}
+
+impl RangeSet {
+	pub fn new() -> RangeSet {
+		RangeSet::default()
+	}
+
+	/// Sorts `ranges` and merges the overlapping and adjacent ones
+	fn normalize(mut ranges: Vec<Range<i64>>) -> RangeSet {
+		ranges.retain(|range| !range.is_empty());
+		ranges.sort_unstable_by_key(|range| range.start);
+
+		let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+		for range in ranges {
+			match merged.last_mut() {
+				Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+				_ => merged.push(range),
+			}
+		}
+		RangeSet { ranges: merged }
+	}
+
+	pub fn ranges(&self) -> &[Range<i64>] {
+		&self.ranges
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ranges.is_empty()
+	}
+
+	/// Number of integers in the set
+	pub fn len(&self) -> i64 {
+		self.ranges
+			.iter()
+			.map(|range| range.end - range.start)
+			.sum()
+	}
+
+	pub fn contains(&self, value: i64) -> bool {
+		let idx = self.ranges.partition_point(|range| range.end <= value);
+		self.ranges
+			.get(idx)
+			.is_some_and(|range| range.contains(&value))
+	}
+
+	pub fn min(&self) -> Option<i64> {
+		self.ranges.first().map(|range| range.start)
+	}
+
+	pub fn max(&self) -> Option<i64> {
+		self.ranges.last().map(|range| range.end - 1)
+	}
+
+	pub fn insert(&mut self, range: Range<i64>) {
+		let mut ranges = std::mem::take(&mut self.ranges);
+		ranges.push(range);
+		*self = RangeSet::normalize(ranges);
+	}
+
+	pub fn union(&self, other: &RangeSet) -> RangeSet {
+		RangeSet::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+	}
+
+	pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+		let mut out = Vec::new();
+		let (mut a, mut b) = (
+			self.ranges.iter().peekable(),
+			other.ranges.iter().peekable(),
+		);
+		while let (Some(range_a), Some(range_b)) = (a.peek(), b.peek()) {
+			let start = range_a.start.max(range_b.start);
+			let end = range_a.end.min(range_b.end);
+			if start < end {
+				out.push(start..end);
+			}
+			// The range ending first can't overlap anything further
+			if range_a.end <= range_b.end {
+				a.next();
+			} else {
+				b.next();
+			}
+		}
+		RangeSet { ranges: out }
+	}
+
+	/// The integers of `self` that aren't in `other`
+	pub fn difference(&self, other: &RangeSet) -> RangeSet {
+		let mut out = Vec::new();
+		let mut others = other.ranges.iter().peekable();
+		for range in &self.ranges {
+			let mut start = range.start;
+			while start < range.end {
+				// Skip the ranges of other entirely before what's left
+				while others.next_if(|other| other.end <= start).is_some() {}
+				match others.peek() {
+					Some(other) if other.start < range.end => {
+						if start < other.start {
+							out.push(start..other.start);
+						}
+						start = other.end;
+					}
+					_ => {
+						out.push(start..range.end);
+						break;
+					}
+				}
+			}
+		}
+		RangeSet { ranges: out }
+	}
+
+	/// Every integer moved by `offset`
+	pub fn shift(&self, offset: i64) -> RangeSet {
+		RangeSet {
+			ranges: self
+				.ranges
+				.iter()
+				.map(|range| (range.start + offset)..(range.end + offset))
+				.collect(),
+		}
+	}
+}
+
+impl From<Range<i64>> for RangeSet {
+	fn from(range: Range<i64>) -> RangeSet {
+		RangeSet::normalize(vec![range])
+	}
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+	fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> RangeSet {
+		RangeSet::normalize(iter.into_iter().collect())
+	}
+}
+
+/// Moves the integers of source ranges by the offset of the range, leaving the others where they
+/// are. When source ranges overlap, the first one inserted applies.
+///
+/// This makes it a piecewise-linear function with a slope of 1, and maps compose with
+/// [`RangeMap::then`] into one such function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+	entries: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+	pub fn new() -> RangeMap {
+		RangeMap::default()
+	}
+
+	/// Maps `source` to the range of the same length starting at `destination`
+	pub fn insert(&mut self, source: Range<i64>, destination: i64) {
+		let offset = destination - source.start;
+		self.entries.push((source, offset));
+	}
+
+	/// Where `value` ends up
+	pub fn get(&self, value: i64) -> i64 {
+		self.entries
+			.iter()
+			.find(|(source, _)| source.contains(&value))
+			.map_or(value, |(_, offset)| value + offset)
+	}
+
+	/// Where all integers of `set` end up
+	pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+		let mut out = RangeSet::new();
+		let mut unmapped = set.clone();
+		for (source, offset) in &self.entries {
+			let source = RangeSet::from(source.clone());
+			out = out.union(&unmapped.intersection(&source).shift(*offset));
+			unmapped = unmapped.difference(&source);
+		}
+		out.union(&unmapped)
+	}
+
+	/// Disjoint source ranges sorted by start, with the offset that applies to each, leaving out
+	/// the ones that don't move anything
+	pub fn pieces(&self) -> Vec<(Range<i64>, i64)> {
+		let mut pieces = Vec::new();
+		let mut covered = RangeSet::new();
+		for (source, offset) in &self.entries {
+			let source = RangeSet::from(source.clone());
+			if *offset != 0 {
+				for range in source.difference(&covered).ranges() {
+					pieces.push((range.clone(), *offset));
+				}
+			}
+			covered = covered.union(&source);
+		}
+		pieces.sort_unstable_by_key(|(range, _)| range.start);
+
+		// Neighbours moving by the same offset are one piece
+		let mut merged: Vec<(Range<i64>, i64)> = Vec::with_capacity(pieces.len());
+		for (range, offset) in pieces {
+			match merged.last_mut() {
+				Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+					last.end = range.end;
+				}
+				_ => merged.push((range, offset)),
+			}
+		}
+		merged
+	}
+
+	fn from_pieces(pieces: Vec<(Range<i64>, i64)>) -> RangeMap {
+		let mut map = RangeMap {
+			entries: pieces
+				.into_iter()
+				.filter(|(range, _)| !range.is_empty())
+				.collect(),
+		};
+		map.entries = map.pieces();
+		map
+	}
+
+	/// The map applying `self`, then `next`
+	pub fn then(&self, next: &RangeMap) -> RangeMap {
+		let next_pieces = next.pieces();
+		let mut pieces = Vec::new();
+		let mut sources = RangeSet::new();
+		for (source, offset) in self.pieces() {
+			let mut image = RangeSet::from(source.clone()).shift(offset);
+			for (next_source, next_offset) in &next_pieces {
+				let next_source = RangeSet::from(next_source.clone());
+				for range in image.intersection(&next_source).ranges() {
+					pieces.push((shift(range, -offset), offset + next_offset));
+				}
+				image = image.difference(&next_source);
+			}
+			pieces.extend(
+				image
+					.ranges()
+					.iter()
+					.map(|range| (shift(range, -offset), offset)),
+			);
+			sources.insert(source);
+		}
+		// What `self` leaves in place only goes through `next`
+		for (next_source, next_offset) in next_pieces {
+			for range in RangeSet::from(next_source).difference(&sources).ranges() {
+				pieces.push((range.clone(), next_offset));
+			}
+		}
+		RangeMap::from_pieces(pieces)
+	}
+
+	/// The integers mapped into `set`
+	pub fn preimage(&self, set: &RangeSet) -> RangeSet {
+		let mut out = RangeSet::new();
+		let mut sources = RangeSet::new();
+		for (source, offset) in self.pieces() {
+			let source = RangeSet::from(source);
+			out = out.union(&set.intersection(&source.shift(offset)).shift(-offset));
+			sources = sources.union(&source);
+		}
+		out.union(&set.difference(&sources))
+	}
+}
+
+fn shift(range: &Range<i64>, offset: i64) -> Range<i64> {
+	(range.start + offset)..(range.end + offset)
+}
+
+/// One `start..end -> start..end` line per piece, empty for the identity
+impl Display for RangeMap {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		for (source, offset) in self.pieces() {
+			writeln!(f, "{:?} -> {:?}", source, shift(&source, offset))?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{RangeMap, RangeSet};
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+	use std::collections::BTreeSet;
+	use std::ops::Range;
+
+	fn points(set: &RangeSet) -> BTreeSet<i64> {
+		set.ranges().iter().cloned().flatten().collect()
+	}
+
+	fn from_points(points: impl IntoIterator<Item = i64>) -> RangeSet {
+		points.into_iter().map(|point| point..point + 1).collect()
+	}
+
+	fn ranges() -> impl Strategy<Value = Vec<Range<i64>>> {
+		vec(
+			(-50i64..50, 0i64..20).prop_map(|(start, len)| start..start + len),
+			0..6,
+		)
+	}
+
+	#[test]
+	fn normalize() {
+		let set: RangeSet = [5..8, 0..2, 2..3, 7..10, 4..4].into_iter().collect();
+		assert_eq!(set.ranges(), [0..3, 5..10]);
+		assert_eq!((set.len(), set.min(), set.max()), (8, Some(0), Some(9)));
+		assert!(set.contains(2) && !set.contains(3) && set.contains(9) && !set.contains(10));
+	}
+
+	#[test]
+	fn map_set() {
+		// A range covering a source range on both sides
+		let mut map = RangeMap::new();
+		map.insert(10..20, 100);
+		assert_eq!(
+			map.map_set(&RangeSet::from(0..30)).ranges(),
+			[0..10, 20..30, 100..110]
+		);
+		assert_eq!((map.get(9), map.get(10), map.get(19)), (9, 100, 109));
+	}
+
+	#[test]
+	fn compose() {
+		let mut a = RangeMap::new();
+		a.insert(0..10, 10);
+		a.insert(5..20, 30);
+		let mut b = RangeMap::new();
+		b.insert(15..25, 0);
+
+		assert_eq!(a.to_string(), "0..10 -> 10..20\n10..20 -> 35..45\n");
+		let composed = a.then(&b);
+		assert_eq!(
+			composed.to_string(),
+			"0..5 -> 10..15\n5..10 -> 0..5\n10..20 -> 35..45\n20..25 -> 5..10\n"
+		);
+		assert_eq!(
+			composed.preimage(&RangeSet::from(0..12)).ranges(),
+			[0..2, 5..10, 20..25]
+		);
+		assert_eq!(RangeMap::new().to_string(), "");
+	}
+
+	proptest! {
+		#[test]
+		fn set_operations(a in ranges(), b in ranges(), offset in -20i64..20) {
+			let (a, b): (RangeSet, RangeSet) = (a.into_iter().collect(), b.into_iter().collect());
+			let (points_a, points_b) = (points(&a), points(&b));
+
+			prop_assert_eq!(a.union(&b), from_points(points_a.union(&points_b).copied()));
+			prop_assert_eq!(
+				a.intersection(&b),
+				from_points(points_a.intersection(&points_b).copied())
+			);
+			prop_assert_eq!(
+				a.difference(&b),
+				from_points(points_a.difference(&points_b).copied())
+			);
+			prop_assert_eq!(a.shift(offset), from_points(points_a.iter().map(|p| p + offset)));
+			prop_assert_eq!(a.len(), points_a.len() as i64);
+		}
+
+		#[test]
+		fn map_set_matches_get(
+			entries in vec((-50i64..50, 0i64..20, -50i64..50), 0..5),
+			set in ranges(),
+		) {
+			let mut map = RangeMap::new();
+			for (start, len, destination) in entries {
+				map.insert(start..start + len, destination);
+			}
+			let set: RangeSet = set.into_iter().collect();
+
+			prop_assert_eq!(
+				map.map_set(&set),
+				from_points(points(&set).into_iter().map(|point| map.get(point)))
+			);
+		}
+
+		#[test]
+		fn then_and_preimage_match_get(
+			first in vec((-50i64..50, 0i64..20, -50i64..50), 0..5),
+			second in vec((-50i64..50, 0i64..20, -50i64..50), 0..5),
+			target in ranges(),
+		) {
+			let map = |entries: Vec<(i64, i64, i64)>| {
+				let mut map = RangeMap::new();
+				for (start, len, destination) in entries {
+					map.insert(start..start + len, destination);
+				}
+				map
+			};
+			let (first, second) = (map(first), map(second));
+			let composed = first.then(&second);
+			let target: RangeSet = target.into_iter().collect();
+
+			// Everything moves by less than 200, so points further out map outside of the target
+			for point in -300..300 {
+				prop_assert_eq!(composed.get(point), second.get(first.get(point)));
+			}
+			prop_assert_eq!(
+				composed.preimage(&target),
+				from_points((-300..300).filter(|point| target.contains(composed.get(*point))))
+			);
+		}
+	}
+}
@@ -0,0 +1,247 @@
+//! Accepted answers, recorded in `answers.toml` at the workspace root as
+//!
+//! ```toml
+//! [day_1]
+//! part_1 = "54644"
+//! part_2 = "53348"
+//! ```
+//!
+//! Crates in year directories get a table per year, like `[2023.day_1]`.
+//!
+//! The file is edited with toml_edit, so notes added next to the answers survive new submissions.
+
+use crate::changes::Changes;
+use crate::day_crate::{DayCrate, Layout};
+use crate::runner::{self, DayRun};
+use crate::table::{Align, Table};
+use aoc_lib::format_duration;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+use toml_edit::{DocumentMut, Item, Table as TomlTable};
+
+pub static ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Default)]
+pub struct Answers {
+	doc: DocumentMut,
+}
+
+impl Answers {
+	/// Reads answers from `path`, treating a missing file as an empty one
+	pub fn load(path: &Path) -> anyhow::Result<Answers> {
+		if !path.exists() {
+			return Ok(Answers::default());
+		}
+		Ok(Answers {
+			doc: crate::manifest::read(path)?,
+		})
+	}
+
+	pub fn save(&self, changes: &Changes, path: &Path) -> anyhow::Result<()> {
+		crate::manifest::write(changes, path, &self.doc)
+	}
+
+	pub fn get(&self, day_crate: &DayCrate, part: u32) -> Option<&str> {
+		self.day_table(day_crate)?
+			.get(&format!("part_{}", part))?
+			.as_str()
+	}
+
+	pub fn set(&mut self, day_crate: &DayCrate, part: u32, answer: &str) -> anyhow::Result<()> {
+		let not_a_table =
+			|key: &str| anyhow::anyhow!("\"{}\" in {} is not a table", key, ANSWERS_FILE);
+
+		let mut table = self.doc.as_table_mut();
+		if let Some(year) = day_crate.year {
+			let year = year.to_string();
+			let mut year_table = TomlTable::new();
+			// Only the [YYYY.day_N] headers are written
+			year_table.set_implicit(true);
+			table = table
+				.entry(&year)
+				.or_insert(Item::Table(year_table))
+				.as_table_mut()
+				.ok_or_else(|| not_a_table(&year))?;
+		}
+		let day_key = format!("day_{}", day_crate.day);
+		let day = table
+			.entry(&day_key)
+			.or_insert(toml_edit::table())
+			.as_table_mut()
+			.ok_or_else(|| not_a_table(&day_key))?;
+		day.insert(&format!("part_{}", part), toml_edit::value(answer));
+		Ok(())
+	}
+
+	fn is_recorded(&self, day_crate: &DayCrate) -> bool {
+		self.day_table(day_crate).is_some()
+	}
+
+	fn day_table(&self, day_crate: &DayCrate) -> Option<&dyn toml_edit::TableLike> {
+		let mut item = self.doc.as_item();
+		if let Some(year) = day_crate.year {
+			item = item.get(year.to_string())?;
+		}
+		item.get(format!("day_{}", day_crate.day))?.as_table_like()
+	}
+}
+
+/// Stores an answer the site accepted
+pub fn record(
+	changes: &Changes,
+	day_crate: &DayCrate,
+	part: u32,
+	answer: &str,
+) -> anyhow::Result<()> {
+	let path = Path::new(ANSWERS_FILE);
+	let mut answers = Answers::load(path)?;
+	answers.set(day_crate, part, answer)?;
+	answers.save(changes, path)?;
+
+	println!("Answer recorded in {}", ANSWERS_FILE);
+
+	Ok(())
+}
+
+/// Runs the selected days and compares what they print with the recorded answers. Days without
+/// recorded answers aren't run, as they may not even be solved yet.
+pub fn verify(
+	year: Option<u32>,
+	layout: Layout,
+	day: Option<u32>,
+	timeout: Duration,
+) -> anyhow::Result<()> {
+	let answers = Answers::load(Path::new(ANSWERS_FILE))?;
+	let day_crates = runner::select_day_crates(year, layout, day)?;
+	let recorded: Vec<DayCrate> = day_crates
+		.iter()
+		.filter(|day_crate| answers.is_recorded(day_crate))
+		.copied()
+		.collect();
+	let mut runs: BTreeMap<DayCrate, DayRun> = if recorded.is_empty() {
+		BTreeMap::new()
+	} else {
+		runner::run_day_crates(recorded, None, timeout)?
+			.into_iter()
+			.map(|run| (run.day_crate, run))
+			.collect()
+	};
+
+	let mut table = Table::new([
+		("Day", Align::Right),
+		("Part", Align::Right),
+		("Expected", Align::Left),
+		("Actual", Align::Left),
+		("Result", Align::Left),
+		("Time", Align::Right),
+	]);
+	let mut failed = 0;
+	for day_crate in day_crates {
+		let Some(run) = runs.remove(&day_crate) else {
+			table.push_row(vec![
+				day_crate.label(),
+				String::from("-"),
+				String::new(),
+				String::new(),
+				String::from("no recorded answers"),
+				String::new(),
+			]);
+			continue;
+		};
+
+		for part in 1..=2 {
+			let expected = match answers.get(&run.day_crate, part) {
+				Some(expected) => expected,
+				None => continue,
+			};
+			let part_run = run.parts.iter().find(|part_run| part_run.part == part);
+			let (actual, result) = match (part_run, &run.error) {
+				(Some(part_run), _) if part_run.answer == expected => {
+					(part_run.answer.clone(), "ok")
+				}
+				(Some(part_run), _) => (part_run.answer.clone(), "DIFFERS"),
+				(None, Some(err)) => (format!("error: {}", err), "FAILED"),
+				(None, None) => (String::new(), "MISSING"),
+			};
+			if result != "ok" {
+				failed += 1;
+			}
+			table.push_row(vec![
+				run.day_crate.label(),
+				part.to_string(),
+				expected.to_string(),
+				actual,
+				result.to_string(),
+				part_run
+					.and_then(|part_run| part_run.time.clone())
+					.unwrap_or_else(|| format_duration(run.time)),
+			]);
+		}
+	}
+
+	println!();
+	print!("{}", table);
+
+	if failed > 0 {
+		anyhow::bail!("{} answer(s) don't match {}", failed, ANSWERS_FILE);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Answers;
+	use crate::changes::Changes;
+	use crate::day_crate::{DayCrate, Layout};
+	use std::fs;
+
+	const DAY_1: DayCrate = DayCrate { year: None, day: 1 };
+
+	#[test]
+	fn record_keeps_comments() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(super::ANSWERS_FILE);
+		fs::write(&path, "[day_1]\npart_1 = \"54644\" # first try\n").unwrap();
+
+		let mut answers = Answers::load(&path).unwrap();
+		assert_eq!(answers.get(&DAY_1, 1), Some("54644"));
+		assert_eq!(answers.get(&DAY_1, 2), None);
+
+		answers.set(&DAY_1, 2, "53348").unwrap();
+		answers
+			.set(&DayCrate::new(Layout::Flat, 2023, 2), 1, "2237")
+			.unwrap();
+		answers.save(&Changes::default(), &path).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(&path).unwrap(),
+			"[day_1]\npart_1 = \"54644\" # first try\npart_2 = \"53348\"\n\n[day_2]\npart_1 = \"2237\"\n"
+		);
+	}
+
+	#[test]
+	fn missing_file_is_empty() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let answers = Answers::load(&dir.path().join(super::ANSWERS_FILE)).unwrap();
+		assert!(!answers.is_recorded(&DAY_1));
+	}
+
+	#[test]
+	fn year_tables() {
+		let mut answers = Answers::default();
+		let day_1_2022 = DayCrate::new(Layout::YearDirs, 2022, 1);
+		answers.set(&day_1_2022, 1, "69528").unwrap();
+		answers
+			.set(&DayCrate::new(Layout::YearDirs, 2023, 1), 1, "54644")
+			.unwrap();
+
+		assert_eq!(
+			answers.doc.to_string(),
+			"[2022.day_1]\npart_1 = \"69528\"\n\n[2023.day_1]\npart_1 = \"54644\"\n"
+		);
+		assert_eq!(answers.get(&day_1_2022, 1), Some("69528"));
+		assert!(!answers.is_recorded(&DAY_1));
+	}
+}
@@ -0,0 +1,29 @@
+//! Serves a fixture directory as a local stand-in for adventofcode.com.
+//!
+//! Usage: `aoc_fixture_server <fixtures dir> [port]`, then point aqa_aoc at it with
+//! `--base-url=http://127.0.0.1:<port>` or `base_url` in aoc_cfg.toml.
+
+#[allow(dead_code)]
+#[path = "../fixture_server.rs"]
+mod fixture_server;
+
+use anyhow::Context;
+use fixture_server::FixtureServer;
+
+fn main() -> anyhow::Result<()> {
+	let mut args = std::env::args().skip(1);
+	let dir = args
+		.next()
+		.context("usage: aoc_fixture_server <fixtures dir> [port]")?;
+	let port = match args.next() {
+		Some(port) => port.parse::<u16>().context("invalid port")?,
+		None => 8080,
+	};
+
+	let server = FixtureServer::serve_dir(port, &dir)
+		.with_context(|| format!("failed to start server on port {}", port))?;
+	println!("Serving {} on {}", dir, server.addr());
+	server.join();
+
+	Ok(())
+}
@@ -0,0 +1,61 @@
+//! Stars from the calendar page of an event
+
+use crate::client::AocClient;
+use scraper::{Html, Selector};
+use std::collections::BTreeMap;
+
+pub fn get_stars(client: &AocClient, year: u32) -> anyhow::Result<BTreeMap<u32, u32>> {
+	let html = client.get(&format!("/{}", year), "fetch calendar")?;
+	Ok(parse_stars(&html))
+}
+
+/// Returns the number of stars of every unlocked day. Locked days aren't links on the calendar,
+/// so they're left out.
+pub fn parse_stars(html: &str) -> BTreeMap<u32, u32> {
+	let document = Html::parse_document(html);
+	let selector = Selector::parse("pre.calendar a[href]").unwrap();
+
+	document
+		.select(&selector)
+		.filter_map(|day| {
+			let number = day
+				.value()
+				.attr("href")?
+				.rsplit_once("/day/")?
+				.1
+				.parse::<u32>()
+				.ok()?;
+			let stars = if day.value().has_class(
+				"calendar-verycomplete",
+				scraper::CaseSensitivity::CaseSensitive,
+			) {
+				2
+			} else if day
+				.value()
+				.has_class("calendar-complete", scraper::CaseSensitivity::CaseSensitive)
+			{
+				1
+			} else {
+				0
+			};
+			Some((number, stars))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::client::test_client;
+	use crate::fixture_server::{FixtureServer, FIXTURES_DIR};
+	use std::collections::BTreeMap;
+
+	#[test]
+	fn stars_from_fixture_server() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+
+		let stars = super::get_stars(&test_client(&server.addr(), cache_dir.path()), 2023).unwrap();
+		assert_eq!(stars, BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+		assert_eq!(server.requests()[0].path, "/2023");
+	}
+}
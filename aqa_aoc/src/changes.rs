@@ -0,0 +1,292 @@
+//! Filesystem changes, made right away or, with `--dry-run`, printed instead.
+//!
+//! A dry run keeps the files it would write in memory, so later steps see them: the manifest of a
+//! crate that would be created can still get its dependencies added. Whatever would fail, like
+//! writing into a missing directory, fails the same way as it would for real.
+
+use anyhow::Context;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lines around changed ones shown in manifest diffs
+const DIFF_CONTEXT: usize = 2;
+
+#[derive(Debug, Clone)]
+enum Planned {
+	Dir,
+	File(String),
+	Removed,
+}
+
+#[derive(Default)]
+pub struct Changes {
+	dry_run: bool,
+	planned: RefCell<BTreeMap<PathBuf, Planned>>,
+}
+
+impl Changes {
+	pub fn new(dry_run: bool) -> Changes {
+		Changes {
+			dry_run,
+			planned: RefCell::default(),
+		}
+	}
+
+	pub fn is_dry_run(&self) -> bool {
+		self.dry_run
+	}
+
+	fn planned(&self, path: &Path) -> Option<Planned> {
+		self.planned.borrow().get(path).cloned()
+	}
+
+	pub fn exists(&self, path: &Path) -> bool {
+		match self.planned(path) {
+			Some(Planned::Removed) => false,
+			Some(_) => true,
+			None => path.exists(),
+		}
+	}
+
+	fn is_dir(&self, path: &Path) -> bool {
+		match self.planned(path) {
+			Some(Planned::Dir) => true,
+			Some(_) => false,
+			None => path.as_os_str().is_empty() || path.is_dir(),
+		}
+	}
+
+	pub fn read_to_string(&self, path: &Path) -> anyhow::Result<String> {
+		match self.planned(path) {
+			Some(Planned::File(contents)) => Ok(contents),
+			Some(_) => anyhow::bail!("failed to read {}: not a file", path.display()),
+			None => fs::read_to_string(path)
+				.with_context(|| format!("failed to read {}", path.display())),
+		}
+	}
+
+	pub fn create_dir_all(&self, path: &Path) -> anyhow::Result<()> {
+		if !self.dry_run {
+			return fs::create_dir_all(path)
+				.with_context(|| format!("failed to create {}", path.display()));
+		}
+		if self.is_dir(path) {
+			return Ok(());
+		}
+
+		let missing: Vec<&Path> = path
+			.ancestors()
+			.take_while(|dir| !self.is_dir(dir))
+			.collect();
+		let existing = missing.last().and_then(|dir| dir.parent());
+		for dir in &missing {
+			if self.exists(dir) {
+				anyhow::bail!(
+					"failed to create {}: {} is not a directory",
+					path.display(),
+					dir.display()
+				);
+			}
+		}
+		if let Some(existing) = existing.filter(|dir| is_read_only(dir)) {
+			anyhow::bail!(
+				"failed to create {}: {} is read-only",
+				path.display(),
+				existing.display()
+			);
+		}
+
+		println!("Would create directory {}", path.display());
+		let mut planned = self.planned.borrow_mut();
+		for dir in missing {
+			planned.insert(dir.to_path_buf(), Planned::Dir);
+		}
+		Ok(())
+	}
+
+	/// Writes `contents` to `path`, whose directory must exist. A dry run prints a diff of
+	/// changed manifests.
+	pub fn write(&self, path: &Path, contents: &str) -> anyhow::Result<()> {
+		if !self.dry_run {
+			return fs::write(path, contents)
+				.with_context(|| format!("failed to write {}", path.display()));
+		}
+
+		let dir = path.parent().unwrap_or(Path::new(""));
+		if !self.is_dir(dir) {
+			anyhow::bail!(
+				"failed to write {}: {} doesn't exist",
+				path.display(),
+				dir.display()
+			);
+		}
+		if self.is_dir(path) {
+			anyhow::bail!("failed to write {}: it's a directory", path.display());
+		}
+
+		if self.exists(path) {
+			if self.planned(path).is_none() && is_read_only(path) {
+				anyhow::bail!("failed to write {}: it's read-only", path.display());
+			}
+			let old = self.read_to_string(path)?;
+			if old != contents {
+				if path.extension().is_some_and(|ext| ext == "toml") {
+					println!("Would change {}:", path.display());
+					print!("{}", diff(&old, contents));
+				} else {
+					println!("Would overwrite {}", path.display());
+				}
+			}
+		} else {
+			if is_read_only(dir) {
+				anyhow::bail!(
+					"failed to write {}: {} is read-only",
+					path.display(),
+					dir.display()
+				);
+			}
+			println!("Would create {}", path.display());
+		}
+
+		self.planned
+			.borrow_mut()
+			.insert(path.to_path_buf(), Planned::File(contents.to_string()));
+		Ok(())
+	}
+
+	pub fn rename(&self, from: &Path, to: &Path) -> anyhow::Result<()> {
+		if !self.dry_run {
+			return fs::rename(from, to)
+				.with_context(|| format!("failed to move {} to {}", from.display(), to.display()));
+		}
+
+		let contents = self
+			.read_to_string(from)
+			.with_context(|| format!("failed to move {} to {}", from.display(), to.display()))?;
+		println!("Would move {} to {}", from.display(), to.display());
+		let mut planned = self.planned.borrow_mut();
+		planned.insert(from.to_path_buf(), Planned::Removed);
+		planned.insert(to.to_path_buf(), Planned::File(contents));
+		Ok(())
+	}
+}
+
+fn is_read_only(path: &Path) -> bool {
+	fs::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly())
+}
+
+/// Line diff of `old` and `new` in the style of `diff -u`, without hunk headers. Runs of unchanged
+/// lines are cut down to [`DIFF_CONTEXT`] lines around the changes.
+pub fn diff(old: &str, new: &str) -> String {
+	let old: Vec<&str> = old.lines().collect();
+	let new: Vec<&str> = new.lines().collect();
+
+	// Longest common subsequence lengths of the suffixes
+	let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			lcs[i][j] = if old[i] == new[j] {
+				lcs[i + 1][j + 1] + 1
+			} else {
+				lcs[i + 1][j].max(lcs[i][j + 1])
+			};
+		}
+	}
+
+	let mut lines: Vec<(char, &str)> = Vec::new();
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() || j < new.len() {
+		if i < old.len() && j < new.len() && old[i] == new[j] {
+			lines.push((' ', old[i]));
+			i += 1;
+			j += 1;
+		} else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+			lines.push(('-', old[i]));
+			i += 1;
+		} else {
+			lines.push(('+', new[j]));
+			j += 1;
+		}
+	}
+
+	let changed: Vec<usize> = (0..lines.len())
+		.filter(|&idx| lines[idx].0 != ' ')
+		.collect();
+	let mut out = String::new();
+	let mut skipped = false;
+	for (idx, (sign, line)) in lines.iter().enumerate() {
+		let near_change = changed
+			.iter()
+			.any(|&changed| changed.abs_diff(idx) <= DIFF_CONTEXT);
+		if near_change {
+			out.push_str(&format!("{}{}\n", sign, line));
+			skipped = false;
+		} else if !skipped {
+			out.push_str(" ...\n");
+			skipped = true;
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Changes;
+	use std::fs;
+
+	#[test]
+	fn diff() {
+		let old = "[workspace]\nmembers = [\n\t\"aoc_lib\",\n\t\"day_1\",\n\t\"day_2\",\n\t\"day_3\",\n\t\"day_4\",\n]\n";
+		let new = "[workspace]\nmembers = [\n\t\"aoc_lib\",\n\t\"day_1\",\n\t\"day_2\",\n\t\"day_3\",\n\t\"day_4\",\n\t\"day_5\",\n]\n";
+		assert_eq!(
+			super::diff(old, new),
+			" ...\n \t\"day_3\",\n \t\"day_4\",\n+\t\"day_5\",\n ]\n"
+		);
+		assert_eq!(
+			super::diff("a = 1\nb = 2\n", "a = 1\nb = 3\n"),
+			" a = 1\n-b = 2\n+b = 3\n"
+		);
+	}
+
+	#[test]
+	fn dry_run() {
+		let dir = tempfile::tempdir().unwrap();
+		let manifest = dir.path().join("Cargo.toml");
+		fs::write(&manifest, "[workspace]\n").unwrap();
+		let changes = Changes::new(true);
+
+		let crate_dir = dir.path().join("2023").join("day_1");
+		changes.create_dir_all(&crate_dir.join("src")).unwrap();
+		changes
+			.write(&crate_dir.join("Cargo.toml"), "[package]\n")
+			.unwrap();
+		assert_eq!(
+			changes
+				.read_to_string(&crate_dir.join("Cargo.toml"))
+				.unwrap(),
+			"[package]\n"
+		);
+		changes
+			.write(&manifest, "[workspace]\nmembers = []\n")
+			.unwrap();
+		changes
+			.rename(&manifest, &dir.path().join("Cargo.toml.bak"))
+			.unwrap();
+		assert!(!changes.exists(&manifest));
+
+		// Would fail for real
+		assert!(changes
+			.write(&dir.path().join("missing").join("input.txt"), "")
+			.is_err());
+		assert!(changes.write(&crate_dir, "").is_err());
+		assert!(changes
+			.create_dir_all(&crate_dir.join("Cargo.toml").join("src"))
+			.is_err());
+
+		// Nothing was touched
+		assert_eq!(fs::read_to_string(&manifest).unwrap(), "[workspace]\n");
+		assert!(!dir.path().join("2023").exists());
+	}
+}
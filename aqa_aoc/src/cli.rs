@@ -0,0 +1,166 @@
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+/// How long run and verify wait for a day by default, as some brute-force solutions take ages
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Advent of Code helper: scaffolds day crates, downloads inputs and puzzles, submits and
+/// checks answers
+#[derive(Debug, Parser)]
+#[command(name = "aqa_aoc", version)]
+pub struct Cli {
+	/// Config file with the year and session cookie. Defaults to the first aoc_cfg.toml in the
+	/// current directory or its parents, then $XDG_CONFIG_HOME/aqa_aoc/config.toml.
+	#[arg(long, global = true)]
+	pub config: Option<PathBuf>,
+	/// Puzzle year, overriding the one from the config file
+	#[arg(long, global = true)]
+	pub year: Option<u32>,
+	/// Day to work with. When fetching, defaults to the first day without an input or to a new
+	/// day after the last one.
+	#[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..=25))]
+	pub day: Option<u32>,
+	/// Address of the Advent of Code site, for example a local fixture server
+	#[arg(long, global = true)]
+	pub base_url: Option<String>,
+	/// Print the files fetch, new and submit would change, with diffs of the manifests, and the
+	/// requests they would send, without doing any of it. Fails if anything would fail.
+	#[arg(long, global = true)]
+	pub dry_run: bool,
+	/// Defaults to `fetch`
+	#[command(subcommand)]
+	pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+	/// Download the input, puzzle and examples of a day, creating its crate if needed
+	Fetch {
+		/// Download the input again even if it's cached or the day already has one
+		#[arg(long)]
+		force: bool,
+		/// Wait for the day to unlock at midnight EST, showing a countdown, then download it
+		#[arg(long)]
+		wait: bool,
+	},
+	/// Create the crate of a day without downloading anything
+	New,
+	/// Submit an answer
+	Submit {
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+		part: u32,
+		answer: String,
+	},
+	/// Build and run the day crates, printing their answers
+	Run {
+		/// Only run this part
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+		part: Option<u32>,
+		/// Stop days still running after this many seconds
+		#[arg(long, default_value_t = DEFAULT_TIMEOUT_SECS)]
+		timeout: u64,
+	},
+	/// Run the day crates and compare their answers with answers.toml
+	Verify {
+		/// Stop days still running after this many seconds
+		#[arg(long, default_value_t = DEFAULT_TIMEOUT_SECS)]
+		timeout: u64,
+	},
+	/// Show the stars of each day from the calendar, which days have crates, inputs, puzzles,
+	/// tests and recorded answers, and what's left to do
+	Status {
+		/// Don't fetch the calendar, showing only what's in the workspace
+		#[arg(long)]
+		offline: bool,
+	},
+	/// Open the puzzle page of a day, or the calendar without --day, in the browser
+	Open,
+	/// Show a private leaderboard with the solve times of every day, or only of --day
+	Leaderboard {
+		/// Leaderboard ID, the number at the end of its URL
+		id: u64,
+		/// Print the JSON from the site instead of tables
+		#[arg(long)]
+		json: bool,
+	},
+	/// Check that the session cookie is valid and print who it belongs to
+	Whoami,
+	/// Print shell completions
+	Completions {
+		#[arg(value_enum)]
+		shell: Shell,
+	},
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Shell {
+	Bash,
+	Zsh,
+	Fish,
+	Powershell,
+	Nushell,
+}
+
+pub fn print_completions(shell: Shell) {
+	match shell {
+		Shell::Bash => generate(clap_complete::Shell::Bash),
+		Shell::Zsh => generate(clap_complete::Shell::Zsh),
+		Shell::Fish => generate(clap_complete::Shell::Fish),
+		Shell::Powershell => generate(clap_complete::Shell::PowerShell),
+		Shell::Nushell => generate(clap_complete_nushell::Nushell),
+	}
+}
+
+fn generate(generator: impl clap_complete::Generator) {
+	clap_complete::generate(
+		generator,
+		&mut Cli::command(),
+		"aqa_aoc",
+		&mut std::io::stdout(),
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cli, Command};
+	use clap::{CommandFactory, Parser};
+
+	#[test]
+	fn command_is_valid() {
+		Cli::command().debug_assert();
+	}
+
+	#[test]
+	fn parse_submit() {
+		let cli =
+			Cli::try_parse_from(["aqa_aoc", "submit", "--day=3", "--part", "1", "4361"]).unwrap();
+
+		assert_eq!(cli.day, Some(3));
+		assert!(matches!(
+			cli.command,
+			Some(Command::Submit { part: 1, answer }) if answer == "4361"
+		));
+	}
+
+	#[test]
+	fn fetch_is_the_default() {
+		let cli = Cli::try_parse_from(["aqa_aoc", "--day=5", "--year", "2022"]).unwrap();
+
+		assert_eq!((cli.day, cli.year), (Some(5), Some(2022)));
+		assert!(cli.command.is_none());
+		assert!(!cli.dry_run);
+
+		let cli = Cli::try_parse_from(["aqa_aoc", "--dry-run"]).unwrap();
+		assert!(cli.dry_run && cli.command.is_none());
+	}
+
+	#[test]
+	fn reject_invalid_arguments() {
+		assert!(Cli::try_parse_from(["aqa_aoc", "--dya=5"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "run", "--part=3"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "fetch", "--day=26"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--force"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "submit", "--part=1"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "leaderboard", "../x"]).is_err());
+	}
+}
@@ -0,0 +1,437 @@
+//! Every request to the Advent of Code site goes through [`AocClient`]. It identifies the tool in
+//! the User-Agent, keeps at least [`MIN_REQUEST_INTERVAL`] between requests, also across runs, and
+//! caches inputs, as they never change once unlocked.
+
+use crate::{input, leaderboard};
+use anyhow::Context;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use ureq::Cookie;
+
+pub const USER_AGENT: &str = concat!(
+	"aqa_aoc/",
+	env!("CARGO_PKG_VERSION"),
+	" (by ",
+	env!("CARGO_PKG_AUTHORS"),
+	")"
+);
+
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Name of the file holding the time of the last request, in milliseconds since the Unix epoch
+const LAST_REQUEST_FILE: &str = "last_request";
+
+/// The site answered with an error status
+#[derive(Debug, Error)]
+#[error("Failed to {action}: {status_line}{}", hint(*.status))]
+pub struct RequestError {
+	pub action: String,
+	pub status: u16,
+	pub status_line: String,
+}
+
+fn hint(status: u16) -> &'static str {
+	match status {
+		// The site answers requests needing a login with 400 Bad Request
+		400 => ", the session cookie may be invalid or expired, check it with `aqa_aoc whoami`",
+		_ => "",
+	}
+}
+
+pub struct AocClient {
+	agent: ureq::Agent,
+	base_url: String,
+	/// Cache directory of the host of `base_url`, so inputs of a fixture server never get mixed
+	/// with the real ones
+	cache_dir: PathBuf,
+	min_interval: Duration,
+}
+
+impl AocClient {
+	pub fn new(base_url: &str, session_cookie: &str, cache_dir: &Path) -> AocClient {
+		let (secure, host) = match base_url.split_once("://") {
+			Some((scheme, rest)) => (scheme == "https", rest),
+			None => (true, base_url),
+		};
+		let host = host.split('/').next().unwrap_or(host);
+		let domain = host.split(':').next().unwrap_or(host);
+
+		let cookie = Cookie::build("session", session_cookie)
+			.domain(domain)
+			.path("/")
+			.secure(secure)
+			.finish()
+			.into_owned();
+
+		let mut agent = ureq::agent();
+		agent.set("User-Agent", USER_AGENT);
+		agent.set_cookie(cookie);
+
+		AocClient {
+			agent,
+			base_url: base_url.trim_end_matches('/').to_string(),
+			cache_dir: cache_dir.join(host.replace(':', "_")),
+			min_interval: MIN_REQUEST_INTERVAL,
+		}
+	}
+
+	pub fn min_interval(mut self, min_interval: Duration) -> AocClient {
+		self.min_interval = min_interval;
+		self
+	}
+
+	/// Returns the cached input of the day, downloading it if it isn't cached or `force` is set.
+	/// Downloaded inputs are checked and normalised with [`input::check`].
+	pub fn input(&self, year: u32, day: u32, force: bool) -> anyhow::Result<String> {
+		self.get_cached(
+			&format!("/{}/day/{}/input", year, day),
+			&input_cache_path(year, day),
+			None,
+			force,
+			"fetch input",
+			|body| Ok(input::check(&body)?),
+		)
+	}
+
+	/// Whether [`AocClient::input`] would use the cached input instead of downloading it
+	pub fn is_input_cached(&self, year: u32, day: u32) -> bool {
+		self.cache_dir.join(input_cache_path(year, day)).is_file()
+	}
+
+	/// Returns the JSON of a private leaderboard. The site asks to fetch it at most once every
+	/// 15 minutes, so newer responses are reused.
+	pub fn leaderboard(&self, year: u32, id: u64) -> anyhow::Result<String> {
+		self.get_cached(
+			&format!("/{}/leaderboard/private/view/{}.json", year, id),
+			&Path::new(&year.to_string()).join(format!("leaderboard_{}.json", id)),
+			Some(LEADERBOARD_MAX_AGE),
+			false,
+			"fetch leaderboard",
+			|body| {
+				leaderboard::parse(&body, id)?;
+				Ok(body)
+			},
+		)
+	}
+
+	/// GET with the response stored at `cache_path` under the cache directory. Cached responses
+	/// are used unless they're older than `max_age` or `force` is set. Responses go through
+	/// `check` before being cached.
+	fn get_cached(
+		&self,
+		path: &str,
+		cache_path: &Path,
+		max_age: Option<Duration>,
+		force: bool,
+		action: &str,
+		check: impl FnOnce(String) -> anyhow::Result<String>,
+	) -> anyhow::Result<String> {
+		let cache_path = self.cache_dir.join(cache_path);
+		let fresh = max_age.is_none_or(|max_age| {
+			fs::metadata(&cache_path)
+				.and_then(|metadata| metadata.modified())
+				.ok()
+				.and_then(|modified| modified.elapsed().ok())
+				.is_some_and(|age| age < max_age)
+		});
+		if fresh && !force {
+			if let Ok(body) = fs::read_to_string(&cache_path) {
+				eprintln!("Using cached response from {}", cache_path.display());
+				return Ok(body);
+			}
+		}
+
+		let body = check(self.get(path, action)?)?;
+
+		fs::create_dir_all(cache_path.parent().unwrap())
+			.and_then(|_| fs::write(&cache_path, &body))
+			.with_context(|| format!("failed to cache the response in {}", cache_path.display()))?;
+		Ok(body)
+	}
+
+	/// Sends a GET request for `path` of the site. `action` describes the request in errors, like
+	/// "fetch input".
+	pub fn get(&self, path: &str, action: &str) -> anyhow::Result<String> {
+		self.send("GET", path, action, |request| request.call())
+	}
+
+	pub fn post_form(
+		&self,
+		path: &str,
+		form: &[(&str, &str)],
+		action: &str,
+	) -> anyhow::Result<String> {
+		self.send("POST", path, action, |request| request.send_form(form))
+	}
+
+	/// The address of `path` of the site
+	pub fn url(&self, path: &str) -> String {
+		format!("{}{}", self.base_url, path)
+	}
+
+	fn send(
+		&self,
+		method: &str,
+		path: &str,
+		action: &str,
+		send: impl FnOnce(&mut ureq::Request) -> ureq::Response,
+	) -> anyhow::Result<String> {
+		self.wait_for_turn()?;
+
+		let req_adr = self.url(path);
+		eprintln!("Request addr: {}", req_adr);
+		let response = send(&mut self.agent.request(method, &req_adr));
+		self.record_request()?;
+
+		if response.synthetic() {
+			// SAFETY: synthetic returns true only when error is Some
+			return Err(response.into_synthetic_error().unwrap().into());
+		}
+
+		if response.error() {
+			return Err(RequestError {
+				action: action.to_string(),
+				status: response.status(),
+				status_line: response.status_line().to_string(),
+			}
+			.into());
+		}
+
+		response.into_string().map_err(Into::into)
+	}
+
+	/// Sleeps until `min_interval` passed since the last request to the host
+	fn wait_for_turn(&self) -> anyhow::Result<()> {
+		let last_request = match fs::read_to_string(self.cache_dir.join(LAST_REQUEST_FILE)) {
+			Ok(millis) => match millis.trim().parse::<u64>() {
+				Ok(millis) => UNIX_EPOCH + Duration::from_millis(millis),
+				Err(_) => return Ok(()),
+			},
+			Err(_) => return Ok(()),
+		};
+
+		// A last request in the future (the clock went back) counts as one made just now
+		let elapsed = SystemTime::now()
+			.duration_since(last_request)
+			.unwrap_or_default();
+		if let Some(wait) = self.min_interval.checked_sub(elapsed) {
+			if !wait.is_zero() {
+				eprintln!("Waiting {:.1}s before the next request", wait.as_secs_f64());
+				std::thread::sleep(wait);
+			}
+		}
+		Ok(())
+	}
+
+	fn record_request(&self) -> anyhow::Result<()> {
+		let millis = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis();
+		let path = self.cache_dir.join(LAST_REQUEST_FILE);
+		fs::create_dir_all(&self.cache_dir)
+			.and_then(|_| fs::write(&path, millis.to_string()))
+			.with_context(|| format!("failed to write {}", path.display()))
+	}
+}
+
+fn input_cache_path(year: u32, day: u32) -> PathBuf {
+	Path::new(&year.to_string())
+		.join(format!("day_{}", day))
+		.join("input.txt")
+}
+
+/// Calls `request` until it succeeds, doubling the delay after each failure that may be temporary:
+/// a 404 of a puzzle that isn't unlocked yet, a server error or a failed connection. Meant for
+/// the first seconds after an unlock, when the site is overloaded.
+pub fn retry<T>(
+	attempts: u32,
+	first_delay: Duration,
+	mut request: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+	let mut delay = first_delay;
+	for _ in 1..attempts {
+		match request() {
+			Err(err) if is_temporary(&err) => {
+				eprintln!("{}, retrying in {:.1}s", err, delay.as_secs_f64());
+				std::thread::sleep(delay);
+				delay *= 2;
+			}
+			result => return result,
+		}
+	}
+	request()
+}
+
+fn is_temporary(err: &anyhow::Error) -> bool {
+	match err.downcast_ref::<RequestError>() {
+		Some(err) => err.status == 404 || err.status >= 500,
+		// Synthetic ureq errors, like a refused connection
+		None => err.is::<ureq::Error>(),
+	}
+}
+
+/// Returns the directory holding cached inputs and the time of the last request, like
+/// `~/.cache/aqa_aoc` on Linux
+pub fn default_cache_dir() -> anyhow::Result<PathBuf> {
+	dirs::cache_dir()
+		.map(|dir| dir.join("aqa_aoc"))
+		.context("failed to find the cache directory")
+}
+
+#[cfg(test)]
+pub fn test_client(addr: &str, cache_dir: &Path) -> AocClient {
+	AocClient::new(addr, "abcd", cache_dir).min_interval(Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{test_client, AocClient, USER_AGENT};
+	use crate::fixture_server::{FixtureServer, FIXTURES_DIR};
+	use std::sync::atomic::{AtomicU32, Ordering};
+	use std::time::Duration;
+
+	#[test]
+	fn get_input_from_fixture_server() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+
+		let input = test_client(&server.addr(), cache_dir.path())
+			.input(2023, 1, false)
+			.unwrap();
+		assert_eq!(
+			input,
+			std::fs::read_to_string(format!("{FIXTURES_DIR}/2023/day/1/input")).unwrap()
+		);
+
+		let requests = server.requests();
+		assert_eq!(requests[0].path, "/2023/day/1/input");
+		assert_eq!(requests[0].header("cookie"), Some("session=abcd"));
+		assert_eq!(requests[0].header("user-agent"), Some(USER_AGENT));
+	}
+
+	#[test]
+	fn get_input_missing_fixture() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+
+		let err = test_client(&server.addr(), cache_dir.path())
+			.input(2023, 25, false)
+			.unwrap_err();
+		assert!(err.to_string().contains("404"), "{err}");
+	}
+
+	#[test]
+	fn logged_out_hint() {
+		let server = FixtureServer::start(|_request| (400, String::from("Please log in"))).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+
+		let err = test_client(&server.addr(), cache_dir.path())
+			.input(2023, 1, false)
+			.unwrap_err();
+		assert!(err.to_string().contains("aqa_aoc whoami"), "{err}");
+	}
+
+	#[test]
+	fn retry_temporary_errors() {
+		let requests = AtomicU32::new(0);
+		let server =
+			FixtureServer::start(
+				move |_request| match requests.fetch_add(1, Ordering::SeqCst) {
+					0 => (404, String::from("404 Not Found")),
+					1 => (502, String::from("Bad Gateway")),
+					_ => (200, String::from("input")),
+				},
+			)
+			.unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		let input = super::retry(5, Duration::ZERO, || client.input(2023, 1, false)).unwrap();
+		assert_eq!(input, "input");
+		assert_eq!(server.requests().len(), 3);
+	}
+
+	#[test]
+	fn retry_gives_up() {
+		let server = FixtureServer::start(|_request| (400, String::from("Please log in"))).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		assert!(super::retry(5, Duration::ZERO, || client.input(2023, 1, false)).is_err());
+		assert_eq!(server.requests().len(), 1);
+
+		let server = FixtureServer::start(|_request| (503, String::new())).unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+		assert!(super::retry(3, Duration::ZERO, || client.input(2023, 2, false)).is_err());
+		assert_eq!(server.requests().len(), 3);
+	}
+
+	#[test]
+	fn inputs_are_cached() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		assert!(!client.is_input_cached(2023, 1));
+		let input = client.input(2023, 1, false).unwrap();
+		assert!(client.is_input_cached(2023, 1));
+		assert_eq!(client.input(2023, 1, false).unwrap(), input);
+		assert_eq!(server.requests().len(), 1);
+
+		assert_eq!(client.input(2023, 1, true).unwrap(), input);
+		assert_eq!(server.requests().len(), 2);
+	}
+
+	#[test]
+	fn leaderboard_login_page_is_not_cached() {
+		let server = FixtureServer::start(|_request| {
+			(200, String::from("<!DOCTYPE html>\n<html>Log in</html>"))
+		})
+		.unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		let err = client.leaderboard(2023, 12345).unwrap_err();
+		assert!(
+			err.to_string().contains("make sure you can view it"),
+			"{err}"
+		);
+		assert!(client.leaderboard(2023, 12345).is_err());
+		assert_eq!(server.requests().len(), 2);
+	}
+
+	#[test]
+	fn requests_are_throttled_across_clients() {
+		let server = FixtureServer::start(|_request| (200, String::from("ok"))).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let interval = Duration::from_millis(300);
+
+		let last_request_file = cache_dir
+			.path()
+			.join(
+				server
+					.addr()
+					.trim_start_matches("http://")
+					.replace(':', "_"),
+			)
+			.join(super::LAST_REQUEST_FILE);
+		let request_times: Vec<u64> = (0..2)
+			.map(|_| {
+				AocClient::new(&server.addr(), "abcd", cache_dir.path())
+					.min_interval(interval)
+					.get("/2023", "fetch calendar")
+					.unwrap();
+				std::fs::read_to_string(&last_request_file)
+					.unwrap()
+					.parse()
+					.unwrap()
+			})
+			.collect();
+		assert!(request_times[1] - request_times[0] >= interval.as_millis() as u64);
+	}
+}
@@ -0,0 +1,177 @@
+//! Finding the config file and the session cookie.
+//!
+//! Without `--config` the first `aoc_cfg.toml` in the current directory or its parents is used,
+//! then `$XDG_CONFIG_HOME/aqa_aoc/config.toml`. The session cookie comes from the `AOC_SESSION`
+//! environment variable, the file named by `session_cookie_file` or `session_cookie`, in that
+//! order, so it doesn't have to live in a file next to the code.
+//!
+//! Commands run from the workspace root, so they work from any day directory: the directory of
+//! the config when it's in the current directory or a parent, otherwise the nearest parent with a
+//! `Cargo.toml` that has a `[workspace]` section.
+
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const CONFIG_FILE: &str = "aoc_cfg.toml";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+	#[error("no {CONFIG_FILE} in {} or its parents and no {}", .start.display(), .global.display())]
+	NotFound { start: PathBuf, global: PathBuf },
+	#[error(
+		"no session cookie, set {SESSION_ENV} or session_cookie or session_cookie_file in the config"
+	)]
+	NoSessionCookie,
+}
+
+/// Returns the config file to use when `--config` isn't given
+pub fn find(start: &Path) -> anyhow::Result<PathBuf> {
+	let global = dirs::config_dir()
+		.context("failed to find the config directory")?
+		.join("aqa_aoc")
+		.join("config.toml");
+	find_in(start, global)
+}
+
+fn find_in(start: &Path, global: PathBuf) -> anyhow::Result<PathBuf> {
+	if let Some(path) = start
+		.ancestors()
+		.map(|dir| dir.join(CONFIG_FILE))
+		.find(|path| path.is_file())
+	{
+		return Ok(path);
+	}
+	if global.is_file() {
+		return Ok(global);
+	}
+	Err(ConfigError::NotFound {
+		start: start.to_path_buf(),
+		global,
+	}
+	.into())
+}
+
+/// Returns the workspace root for `start` given the config in use, `None` when there's none
+pub fn workspace_root(start: &Path, config: Option<&Path>) -> Option<PathBuf> {
+	if let Some(dir) = config.and_then(Path::parent) {
+		if start.starts_with(dir) {
+			return Some(dir.to_path_buf());
+		}
+	}
+	start
+		.ancestors()
+		.find(|dir| {
+			std::fs::read_to_string(dir.join("Cargo.toml"))
+				.ok()
+				.and_then(|manifest| manifest.parse::<toml_edit::DocumentMut>().ok())
+				.is_some_and(|manifest| manifest.contains_key("workspace"))
+		})
+		.map(Path::to_path_buf)
+}
+
+/// Picks the session cookie from `env` (the value of `AOC_SESSION`), `cookie_file` or `cookie`.
+/// A relative `cookie_file` is relative to `config_dir`.
+pub fn session_cookie(
+	env: Option<String>,
+	cookie_file: Option<&Path>,
+	cookie: Option<&str>,
+	config_dir: &Path,
+) -> anyhow::Result<String> {
+	if let Some(env) = env.filter(|env| !env.trim().is_empty()) {
+		return Ok(env.trim().to_string());
+	}
+	if let Some(cookie_file) = cookie_file {
+		let path = config_dir.join(cookie_file);
+		let cookie = std::fs::read_to_string(&path).with_context(|| {
+			format!("failed to read the session cookie from {}", path.display())
+		})?;
+		return match cookie.trim() {
+			"" => Err(anyhow::anyhow!("{} is empty", path.display())),
+			cookie => Ok(cookie.to_string()),
+		};
+	}
+	match cookie.map(str::trim) {
+		Some(cookie) if !cookie.is_empty() => Ok(cookie.to_string()),
+		_ => Err(ConfigError::NoSessionCookie.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{find_in, session_cookie, workspace_root, CONFIG_FILE};
+	use std::fs;
+	use std::path::Path;
+
+	#[test]
+	fn find_config() {
+		let dir = tempfile::tempdir().unwrap();
+		let day_dir = dir.path().join("2023").join("day_1");
+		fs::create_dir_all(&day_dir).unwrap();
+		let global = dir.path().join("global.toml");
+
+		assert!(find_in(&day_dir, global.clone()).is_err());
+
+		fs::write(&global, "").unwrap();
+		assert_eq!(find_in(&day_dir, global.clone()).unwrap(), global);
+
+		let workspace_cfg = dir.path().join("2023").join(CONFIG_FILE);
+		fs::write(&workspace_cfg, "").unwrap();
+		assert_eq!(find_in(&day_dir, global.clone()).unwrap(), workspace_cfg);
+
+		let day_cfg = day_dir.join(CONFIG_FILE);
+		fs::write(&day_cfg, "").unwrap();
+		assert_eq!(find_in(&day_dir, global).unwrap(), day_cfg);
+	}
+
+	#[test]
+	fn workspace_root_from_subdirectory() {
+		let dir = tempfile::tempdir().unwrap();
+		let day_src = dir.path().join("day_1").join("src");
+		fs::create_dir_all(&day_src).unwrap();
+		fs::write(
+			dir.path().join("Cargo.toml"),
+			"[workspace]\nmembers = [\"day_1\"]\n",
+		)
+		.unwrap();
+		fs::write(dir.path().join("day_1").join("Cargo.toml"), "[package]\n").unwrap();
+		let global = dir.path().join("global").join("config.toml");
+
+		assert_eq!(workspace_root(&day_src, None).unwrap(), dir.path());
+		assert_eq!(workspace_root(&day_src, Some(&global)).unwrap(), dir.path());
+
+		let cfg = dir.path().join(CONFIG_FILE);
+		assert_eq!(workspace_root(&day_src, Some(&cfg)).unwrap(), dir.path());
+		let day_cfg = dir.path().join("day_1").join(CONFIG_FILE);
+		assert_eq!(
+			workspace_root(&day_src, Some(&day_cfg)).unwrap(),
+			dir.path().join("day_1")
+		);
+
+		let outside = tempfile::tempdir().unwrap();
+		assert_eq!(workspace_root(outside.path(), None), None);
+	}
+
+	#[test]
+	fn session_cookie_sources() {
+		let dir = tempfile::tempdir().unwrap();
+		fs::write(dir.path().join("session"), "from_file\n").unwrap();
+		let file = Some(Path::new("session"));
+
+		let cookie = |env: Option<&str>, file, cookie| {
+			session_cookie(env.map(String::from), file, cookie, dir.path())
+		};
+		assert_eq!(
+			cookie(Some("from_env"), file, Some("from_cfg")).unwrap(),
+			"from_env"
+		);
+		assert_eq!(
+			cookie(Some(""), file, Some("from_cfg")).unwrap(),
+			"from_file"
+		);
+		assert_eq!(cookie(None, None, Some("from_cfg")).unwrap(), "from_cfg");
+		assert!(cookie(None, Some(Path::new("missing")), Some("from_cfg")).is_err());
+		assert!(cookie(None, None, Some("")).is_err());
+	}
+}
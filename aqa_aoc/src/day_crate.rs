@@ -0,0 +1,152 @@
+//! Where day crates live in the workspace. With the flat layout day N is the `day_N` crate at the
+//! workspace root, with the year directories layout it's `YYYY/day_N`, named `yYYYY_day_N` as
+//! package names must be unique in the workspace.
+
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+	/// `day_N` crates at the workspace root, for workspaces holding a single year
+	#[default]
+	Flat,
+	/// `YYYY/day_N` crates, so several years can live in one workspace
+	YearDirs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DayCrate {
+	/// Set for crates in year directories
+	pub year: Option<u32>,
+	pub day: u32,
+}
+
+impl DayCrate {
+	pub fn new(layout: Layout, year: u32, day: u32) -> DayCrate {
+		DayCrate {
+			year: match layout {
+				Layout::Flat => None,
+				Layout::YearDirs => Some(year),
+			},
+			day,
+		}
+	}
+
+	/// Parses a workspace member like `day_7` or `2023/day_7`
+	pub fn from_member(member: &str) -> Option<DayCrate> {
+		let member = member.trim_end_matches('/');
+		let (year, day_dir) = match member.split_once('/') {
+			Some((year, day_dir)) => (Some(year.parse::<u32>().ok()?), day_dir),
+			None => (None, member),
+		};
+		Some(DayCrate {
+			year,
+			day: day_dir.strip_prefix("day_")?.parse::<u32>().ok()?,
+		})
+	}
+
+	/// Path relative to the workspace root, as listed in `workspace.members`
+	pub fn member(&self) -> String {
+		match self.year {
+			Some(year) => format!("{}/day_{}", year, self.day),
+			None => format!("day_{}", self.day),
+		}
+	}
+
+	pub fn dir(&self) -> PathBuf {
+		PathBuf::from(self.member())
+	}
+
+	pub fn package_name(&self) -> String {
+		match self.year {
+			Some(year) => format!("y{}_day_{}", year, self.day),
+			None => format!("day_{}", self.day),
+		}
+	}
+
+	/// Short form for tables, `7` or `2023/7`
+	pub fn label(&self) -> String {
+		match self.year {
+			Some(year) => format!("{}/{}", year, self.day),
+			None => self.day.to_string(),
+		}
+	}
+
+	/// Whether the crate belongs to `year` in a workspace with `layout`. Crates of the flat layout
+	/// hold the configured year, so they belong to none once the workspace uses year directories.
+	pub fn is_in_year(&self, year: u32, layout: Layout) -> bool {
+		match self.year {
+			Some(y) => y == year,
+			None => layout == Layout::Flat,
+		}
+	}
+}
+
+impl Display for DayCrate {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.year {
+			Some(year) => write!(f, "{} day {}", year, self.day),
+			None => write!(f, "day {}", self.day),
+		}
+	}
+}
+
+/// Returns the day crates of the workspace, ordered by year and day
+pub fn workspace_day_crates(
+	cargo_workspace: &toml_edit::DocumentMut,
+) -> anyhow::Result<Vec<DayCrate>> {
+	let mut day_crates: Vec<DayCrate> = crate::manifest::workspace_members(cargo_workspace)?
+		.iter()
+		.filter_map(|member| DayCrate::from_member(member))
+		.collect();
+	day_crates.sort_unstable();
+	day_crates.dedup();
+	Ok(day_crates)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{DayCrate, Layout};
+
+	#[test]
+	fn from_member() {
+		let flat = DayCrate::from_member("day_7").unwrap();
+		assert_eq!(flat, DayCrate::new(Layout::Flat, 2023, 7));
+		assert_eq!(flat.member(), "day_7");
+		assert_eq!(flat.package_name(), "day_7");
+		assert!(flat.is_in_year(2023, Layout::Flat));
+		assert!(!flat.is_in_year(2024, Layout::YearDirs));
+
+		let year_dir = DayCrate::from_member("2022/day_25").unwrap();
+		assert_eq!(year_dir, DayCrate::new(Layout::YearDirs, 2022, 25));
+		assert_eq!(year_dir.member(), "2022/day_25");
+		assert_eq!(year_dir.package_name(), "y2022_day_25");
+		assert!(year_dir.is_in_year(2022, Layout::YearDirs));
+		assert!(!year_dir.is_in_year(2023, Layout::Flat));
+
+		assert_eq!(DayCrate::from_member("aqa_aoc"), None);
+		assert_eq!(DayCrate::from_member("day_2_nu"), None);
+		assert_eq!(DayCrate::from_member("tools/day_2"), None);
+	}
+
+	#[test]
+	fn workspace_day_crates() {
+		let manifest = "[workspace]
+members = ['aqa_aoc', '2023/day_10', 'day_2', '2022/day_3', '2023/day_9', 'day_1']
+"
+		.parse()
+		.unwrap();
+
+		let members: Vec<String> = super::workspace_day_crates(&manifest)
+			.unwrap()
+			.iter()
+			.map(|day_crate| day_crate.member())
+			.collect();
+		assert_eq!(
+			members,
+			["day_1", "day_2", "2022/day_3", "2023/day_9", "2023/day_10"]
+		);
+	}
+}
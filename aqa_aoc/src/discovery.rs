@@ -0,0 +1,248 @@
+//! Finding the days of a year from the workspace members and the directories holding day crates,
+//! independently of the order the filesystem lists them in.
+
+use crate::day_crate::{self, DayCrate, Layout};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// What the workspace has for a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayState {
+	/// The workspace member, or where the crate goes with the configured layout
+	pub day_crate: DayCrate,
+	/// Listed in `workspace.members`
+	pub is_member: bool,
+	/// The directory of the day crate exists
+	pub has_dir: bool,
+	pub has_input: bool,
+	/// Suffixes of companion directories with solutions in other languages, like `nu` for
+	/// `day_N_nu`
+	pub companions: Vec<String>,
+}
+
+/// Returns the days of `year` that have a workspace member, a crate directory or a companion
+/// directory, ordered by day
+pub fn scan(
+	cargo_workspace: &toml_edit::DocumentMut,
+	workspace_dir: &Path,
+	layout: Layout,
+	year: u32,
+) -> anyhow::Result<Vec<DayState>> {
+	let mut days: BTreeMap<u32, DayState> = BTreeMap::new();
+	let new_state = |day: u32| DayState {
+		day_crate: DayCrate::new(layout, year, day),
+		is_member: false,
+		has_dir: false,
+		has_input: false,
+		companions: Vec::new(),
+	};
+
+	for member in day_crate::workspace_day_crates(cargo_workspace)?
+		.into_iter()
+		.filter(|member| member.is_in_year(year, layout))
+	{
+		let state = days
+			.entry(member.day)
+			.or_insert_with(|| new_state(member.day));
+		state.day_crate = member;
+		state.is_member = true;
+	}
+
+	let base_dir = match layout {
+		Layout::Flat => workspace_dir.to_path_buf(),
+		Layout::YearDirs => workspace_dir.join(year.to_string()),
+	};
+	if base_dir.is_dir() {
+		for entry in fs::read_dir(&base_dir)?.filter_map(Result::ok) {
+			if !entry.path().is_dir() {
+				continue;
+			}
+			let name = entry.file_name();
+			if let Some((day, companion)) = name.to_str().and_then(parse_dir_name) {
+				let state = days.entry(day).or_insert_with(|| new_state(day));
+				state.companions.extend(companion.map(String::from));
+			}
+		}
+	}
+
+	for state in days.values_mut() {
+		let crate_dir = workspace_dir.join(state.day_crate.dir());
+		state.has_dir = crate_dir.is_dir();
+		state.has_input = crate_dir.join("input.txt").is_file();
+		state.companions.sort_unstable();
+	}
+	Ok(days.into_values().collect())
+}
+
+/// Parses `day_N` and companion directory names like `day_N_nu`
+fn parse_dir_name(name: &str) -> Option<(u32, Option<&str>)> {
+	let rest = name.strip_prefix("day_")?;
+	let (day, companion) = match rest.split_once('_') {
+		Some((day, companion)) => (day, Some(companion)),
+		None => (rest, None),
+	};
+	if day.is_empty() || !day.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+	if companion.is_some_and(|companion| {
+		companion.is_empty() || !companion.bytes().all(|b| b.is_ascii_alphanumeric())
+	}) {
+		return None;
+	}
+	Some((day.parse().ok()?, companion))
+}
+
+/// The lowest day without an input yet
+pub fn first_unfinished(days: &[DayState]) -> Option<u32> {
+	days.iter()
+		.find(|state| !state.has_input)
+		.map(|state| state.day_crate.day)
+}
+
+/// The day after the last one
+pub fn next_day(days: &[DayState]) -> u32 {
+	days.last().map_or(0, |state| state.day_crate.day) + 1
+}
+
+#[cfg(test)]
+mod tests {
+	use super::DayState;
+	use crate::day_crate::{DayCrate, Layout};
+	use std::fs;
+	use std::path::Path;
+
+	fn workspace(members: &str, dirs: &[&str], inputs: &[&str]) -> tempfile::TempDir {
+		let dir = tempfile::tempdir().unwrap();
+		fs::write(
+			dir.path().join("Cargo.toml"),
+			format!("[workspace]\nmembers = [{}]\n", members),
+		)
+		.unwrap();
+		for day_dir in dirs {
+			fs::create_dir_all(dir.path().join(day_dir)).unwrap();
+		}
+		for day_dir in inputs {
+			fs::write(dir.path().join(day_dir).join("input.txt"), "").unwrap();
+		}
+		dir
+	}
+
+	fn scan(dir: &Path, layout: Layout) -> Vec<DayState> {
+		let cargo_workspace = crate::manifest::read(&dir.join("Cargo.toml")).unwrap();
+		super::scan(&cargo_workspace, dir, layout, 2023).unwrap()
+	}
+
+	#[test]
+	fn parse_dir_name() {
+		assert_eq!(super::parse_dir_name("day_7"), Some((7, None)));
+		assert_eq!(
+			super::parse_dir_name("day_12_pwsh"),
+			Some((12, Some("pwsh")))
+		);
+		assert_eq!(super::parse_dir_name("day_"), None);
+		assert_eq!(super::parse_dir_name("day_x"), None);
+		assert_eq!(super::parse_dir_name("day_2_"), None);
+		assert_eq!(super::parse_dir_name("day_2_nu.bak"), None);
+		assert_eq!(super::parse_dir_name("aqa_aoc"), None);
+	}
+
+	#[test]
+	fn numeric_order_with_companions() {
+		let dir = workspace(
+			"'aqa_aoc', 'day_10', 'day_1', 'day_2'",
+			&[
+				"aqa_aoc",
+				"day_1",
+				"day_10",
+				"day_2",
+				"day_2_nu",
+				"day_4_nu",
+				"day_4_pwsh",
+			],
+			&["day_1", "day_10"],
+		);
+
+		let days = scan(dir.path(), Layout::Flat);
+		assert_eq!(
+			days,
+			[
+				DayState {
+					day_crate: DayCrate::new(Layout::Flat, 2023, 1),
+					is_member: true,
+					has_dir: true,
+					has_input: true,
+					companions: vec![],
+				},
+				DayState {
+					day_crate: DayCrate::new(Layout::Flat, 2023, 2),
+					is_member: true,
+					has_dir: true,
+					has_input: false,
+					companions: vec![String::from("nu")],
+				},
+				DayState {
+					day_crate: DayCrate::new(Layout::Flat, 2023, 4),
+					is_member: false,
+					has_dir: false,
+					has_input: false,
+					companions: vec![String::from("nu"), String::from("pwsh")],
+				},
+				DayState {
+					day_crate: DayCrate::new(Layout::Flat, 2023, 10),
+					is_member: true,
+					has_dir: true,
+					has_input: true,
+					companions: vec![],
+				},
+			]
+		);
+		assert_eq!(super::first_unfinished(&days), Some(2));
+		assert_eq!(super::next_day(&days), 11);
+	}
+
+	#[test]
+	fn all_finished() {
+		let dir = workspace(
+			"'day_1', 'day_2'",
+			&["day_1", "day_2", "day_2_nu"],
+			&["day_1", "day_2"],
+		);
+
+		let days = scan(dir.path(), Layout::Flat);
+		assert_eq!(super::first_unfinished(&days), None);
+		assert_eq!(super::next_day(&days), 3);
+		assert_eq!(super::next_day(&[]), 1);
+	}
+
+	#[test]
+	fn year_dirs() {
+		let dir = workspace(
+			"'day_1', 'day_9', '2022/day_5', '2023/day_3'",
+			&[
+				"day_1",
+				"day_9",
+				"2022/day_5",
+				"2023/day_1",
+				"2023/day_2",
+				"2023/day_3",
+			],
+			&["day_1", "2023/day_1"],
+		);
+
+		let days: Vec<(String, bool, bool)> = scan(dir.path(), Layout::YearDirs)
+			.iter()
+			.map(|state| (state.day_crate.member(), state.is_member, state.has_input))
+			.collect();
+		// Flat crates are left over from before the switch to year directories and belong to no
+		// year, so day 1 is the unlisted 2023/day_1 rather than the flat day_1
+		assert_eq!(
+			days,
+			[
+				(String::from("2023/day_1"), false, true),
+				(String::from("2023/day_2"), false, false),
+				(String::from("2023/day_3"), true, false),
+			]
+		);
+	}
+}
@@ -0,0 +1,177 @@
+use crate::changes::Changes;
+use scraper::{Html, Selector};
+use std::fmt::Write as _;
+use std::path::Path;
+
+static EXAMPLE_TESTS_FILE: &str = "example_tests.rs";
+
+/// Extracts the `<pre><code>` blocks of the puzzle description, skipping repeated ones (part 2
+/// often reuses the examples of part 1)
+pub fn extract_examples(html: &str) -> Vec<String> {
+	let document = Html::parse_document(html);
+	let selector = Selector::parse("article.day-desc pre > code").unwrap();
+
+	let mut examples: Vec<String> = Vec::new();
+	for code in document.select(&selector) {
+		let example: String = code.text().collect();
+		if !examples.contains(&example) {
+			examples.push(example);
+		}
+	}
+	examples
+}
+
+/// Saves examples as `examples/example_N.txt` in `crate_dir` and generates
+/// `src/example_tests.rs` loading them.
+///
+/// Tests already present in `src/example_tests.rs` are kept as they are, so expected answers
+/// filled in after part 1 survive refetching the puzzle for part 2.
+pub fn write_examples(
+	changes: &Changes,
+	crate_dir: &Path,
+	examples: &[String],
+) -> anyhow::Result<()> {
+	if examples.is_empty() {
+		return Ok(());
+	}
+
+	let examples_dir = crate_dir.join("examples");
+	changes.create_dir_all(&examples_dir)?;
+	for (idx, example) in examples.iter().enumerate() {
+		changes.write(
+			&examples_dir.join(format!("example_{}.txt", idx + 1)),
+			example,
+		)?;
+	}
+
+	let src_dir = crate_dir.join("src");
+	let tests_path = src_dir.join(EXAMPLE_TESTS_FILE);
+	let mut tests = match changes.read_to_string(&tests_path) {
+		Ok(tests) => tests,
+		Err(_) => String::from(
+			"//! Generated by aqa_aoc from the examples on the puzzle page. Fill in the expected\n\
+			 //! answers and remove the `#[ignore]` attributes of the tests that apply.\n\
+			 \n\
+			 use super::Day;\n\
+			 use aoc_lib::Solution;\n",
+		),
+	};
+	let mut tests_changed = false;
+	for example_n in 1..=examples.len() {
+		if !tests.contains(&format!("const EXAMPLE_{}:", example_n)) {
+			tests.push_str(&example_tests(example_n));
+			tests_changed = true;
+		}
+	}
+	if tests_changed {
+		changes.create_dir_all(&src_dir)?;
+		changes.write(&tests_path, &tests)?;
+	}
+
+	let main_path = src_dir.join("main.rs");
+	if let Ok(mut main) = changes.read_to_string(&main_path) {
+		if !main.contains("mod example_tests;") {
+			if !main.ends_with('\n') {
+				main.push('\n');
+			}
+			main.push_str("\n#[cfg(test)]\nmod example_tests;\n");
+			changes.write(&main_path, &main)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn example_tests(example_n: usize) -> String {
+	let mut out = format!(
+		"\nconst EXAMPLE_{example_n}: &str = include_str!(\"../examples/example_{example_n}.txt\");\n"
+	);
+	for part in 1..=2 {
+		write!(
+			out,
+			"
+#[test]
+#[ignore = \"expected answer not filled in\"]
+fn part_{part}_example_{example_n}() {{
+	assert_eq!(Day::solve_part_{part}(EXAMPLE_{example_n}).unwrap().to_string(), \"TODO\");
+}}
+"
+		)
+		.unwrap();
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::changes::Changes;
+	use std::fs;
+
+	#[test]
+	fn extract_from_fixture_page() {
+		let html = fs::read_to_string(concat!(
+			env!("CARGO_MANIFEST_DIR"),
+			"/fixtures/2023/day/1/index.html"
+		))
+		.unwrap();
+
+		assert_eq!(
+			super::extract_examples(&html),
+			vec!["1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"]
+		);
+	}
+
+	#[test]
+	fn extract_skips_duplicates_and_inline_code() {
+		let html = r#"<article class="day-desc"><pre><code>1 2
+</code></pre><p>Produces <code>3</code>.</p></article>
+<article class="day-desc"><pre><code>1 2
+</code></pre><pre><code>4 5
+</code></pre></article>"#;
+
+		assert_eq!(super::extract_examples(html), vec!["1 2\n", "4 5\n"]);
+	}
+
+	#[test]
+	fn write_examples_keeps_existing_tests() {
+		let dir = tempfile::tempdir().unwrap();
+		fs::create_dir(dir.path().join("src")).unwrap();
+		fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+
+		super::write_examples(&Changes::default(), dir.path(), &[String::from("1 2\n")]).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(dir.path().join("examples/example_1.txt")).unwrap(),
+			"1 2\n"
+		);
+		let main = fs::read_to_string(dir.path().join("src/main.rs")).unwrap();
+		assert_eq!(main, "fn main() {}\n\n#[cfg(test)]\nmod example_tests;\n");
+
+		let tests_path = dir.path().join("src/example_tests.rs");
+		let tests = fs::read_to_string(&tests_path).unwrap();
+		assert!(
+			tests.contains("const EXAMPLE_1: &str = include_str!(\"../examples/example_1.txt\");")
+		);
+		assert!(tests.contains("fn part_1_example_1()"));
+		assert!(tests.contains("Day::solve_part_1(EXAMPLE_1)"));
+		assert!(tests.contains("fn part_2_example_1()"));
+
+		// Pretend the expected answer was filled in, then refetch with a part 2 example
+		let filled_in = tests.replacen("\"TODO\"", "\"3\"", 1);
+		fs::write(&tests_path, &filled_in).unwrap();
+		super::write_examples(
+			&Changes::default(),
+			dir.path(),
+			&[String::from("1 2\n"), String::from("4 5\n")],
+		)
+		.unwrap();
+
+		let tests = fs::read_to_string(&tests_path).unwrap();
+		assert!(tests.starts_with(&filled_in));
+		assert!(tests.contains("fn part_2_example_2()"));
+		assert_eq!(
+			fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+			main
+		);
+	}
+}
@@ -0,0 +1,193 @@
+//! Minimal HTTP/1.1 server standing in for adventofcode.com in tests and CI.
+//!
+//! [`FixtureServer::serve_dir`] mirrors the site layout from a directory: a request for `/p` is
+//! answered with the file `{dir}/p`, or `{dir}/p/index.html` if `p` is a directory. So
+//! `fixtures/2023/day/1/input`, `fixtures/2023/day/1/index.html` and
+//! `fixtures/2023/day/1/answer` serve the input, the puzzle page and the answer response of
+//! 2023 day 1. Missing files are answered with 404.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Fixtures of the tests of aqa_aoc
+#[cfg(test)]
+pub const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+#[derive(Debug, Clone)]
+pub struct Request {
+	pub method: String,
+	pub path: String,
+	pub headers: Vec<(String, String)>,
+	pub body: String,
+}
+
+impl Request {
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v.as_str())
+	}
+}
+
+pub struct FixtureServer {
+	port: u16,
+	requests: Arc<Mutex<Vec<Request>>>,
+	shutdown: Arc<AtomicBool>,
+	handle: Option<JoinHandle<()>>,
+}
+
+impl FixtureServer {
+	/// Starts the server on a random port. `handler` returns status code and body.
+	pub fn start<F>(handler: F) -> std::io::Result<FixtureServer>
+	where
+		F: Fn(&Request) -> (u16, String) + Send + 'static,
+	{
+		FixtureServer::start_on(0, handler)
+	}
+
+	/// Starts the server on `port` (0 picks a random one). `handler` returns status code and body.
+	pub fn start_on<F>(port: u16, handler: F) -> std::io::Result<FixtureServer>
+	where
+		F: Fn(&Request) -> (u16, String) + Send + 'static,
+	{
+		let listener = TcpListener::bind(("127.0.0.1", port))?;
+		let port = listener.local_addr()?.port();
+		let requests = Arc::new(Mutex::new(Vec::new()));
+		let shutdown = Arc::new(AtomicBool::new(false));
+
+		let handle = {
+			let requests = Arc::clone(&requests);
+			let shutdown = Arc::clone(&shutdown);
+			std::thread::spawn(move || {
+				for stream in listener.incoming() {
+					if shutdown.load(Ordering::SeqCst) {
+						break;
+					}
+					let Ok(mut stream) = stream else {
+						continue;
+					};
+					let Some(request) = read_request(&mut stream) else {
+						continue;
+					};
+					let (status, body) = handler(&request);
+					requests.lock().unwrap().push(request);
+					let _ = write!(
+						stream,
+						"HTTP/1.1 {status} {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+						if status < 400 { "OK" } else { "Error" },
+						body.len(),
+					);
+				}
+			})
+		};
+
+		Ok(FixtureServer {
+			port,
+			requests,
+			shutdown,
+			handle: Some(handle),
+		})
+	}
+
+	/// Starts the server on `port`, serving files from `dir`
+	pub fn serve_dir(port: u16, dir: impl Into<PathBuf>) -> std::io::Result<FixtureServer> {
+		let dir = dir.into();
+		FixtureServer::start_on(port, move |request| {
+			match fixture_path(&dir, &request.path) {
+				Some(path) => match std::fs::read_to_string(&path) {
+					Ok(body) => (200, body),
+					Err(_) => (404, String::from("404 Not Found")),
+				},
+				None => (404, String::from("404 Not Found")),
+			}
+		})
+	}
+
+	pub fn addr(&self) -> String {
+		format!("http://127.0.0.1:{}", self.port)
+	}
+
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+
+	/// Blocks until the server is shut down
+	#[allow(dead_code)] // only used by the aoc_fixture_server binary
+	pub fn join(mut self) {
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
+
+impl Drop for FixtureServer {
+	fn drop(&mut self) {
+		self.shutdown.store(true, Ordering::SeqCst);
+		// Wake up the listener thread blocked on accept
+		let _ = TcpStream::connect(("127.0.0.1", self.port));
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
+
+/// Maps the request path to a file inside `dir`, refusing to escape it
+fn fixture_path(dir: &Path, request_path: &str) -> Option<PathBuf> {
+	let request_path = request_path.split(['?', '#']).next()?;
+	let relative = Path::new(request_path.trim_start_matches('/'));
+	if relative
+		.components()
+		.any(|c| !matches!(c, Component::Normal(_)))
+	{
+		return None;
+	}
+
+	let path = dir.join(relative);
+	if path.is_dir() {
+		Some(path.join("index.html"))
+	} else {
+		Some(path)
+	}
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<Request> {
+	let mut reader = BufReader::new(stream);
+
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line).ok()?;
+	let mut parts = request_line.split_whitespace();
+	let method = parts.next()?.to_string();
+	let path = parts.next()?.to_string();
+
+	let mut headers = Vec::new();
+	loop {
+		let mut line = String::new();
+		reader.read_line(&mut line).ok()?;
+		let line = line.trim_end();
+		if line.is_empty() {
+			break;
+		}
+		let (name, value) = line.split_once(':')?;
+		headers.push((name.trim().to_string(), value.trim().to_string()));
+	}
+
+	let content_length = headers
+		.iter()
+		.find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+		.and_then(|(_, value)| value.parse::<usize>().ok())
+		.unwrap_or_default();
+	let mut body = vec![0; content_length];
+	reader.read_exact(&mut body).ok()?;
+
+	Some(Request {
+		method,
+		path,
+		headers,
+		body: String::from_utf8(body).ok()?,
+	})
+}
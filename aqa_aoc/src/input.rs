@@ -0,0 +1,147 @@
+//! Checking downloaded inputs and saving them with a checksum
+
+use crate::changes::Changes;
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use thiserror::Error;
+
+pub const INPUT_FILE: &str = "input.txt";
+/// In the format of `sha256sum`, so `sha256sum -c input.txt.sha256` checks the input
+pub const CHECKSUM_FILE: &str = "input.txt.sha256";
+/// Where a replaced input with different content is kept
+pub const BACKUP_FILE: &str = "input.txt.bak";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InputError {
+	#[error("the site sent an empty input")]
+	Empty,
+	#[error("got a web page instead of the input, check the session cookie with `aqa_aoc whoami`")]
+	Html,
+	#[error(
+		"the site doesn't know whose input to send, check the session cookie with `aqa_aoc whoami`"
+	)]
+	LoggedOut,
+	#[error("the site asked not to request the input repeatedly, try again later")]
+	RateLimited,
+}
+
+/// Rejects bodies the site sends instead of an input and normalises line endings to `\n`
+pub fn check(body: &str) -> Result<String, InputError> {
+	let input = body.replace("\r\n", "\n");
+
+	let start = input.trim_start();
+	if start.is_empty() {
+		return Err(InputError::Empty);
+	}
+	// Only full pages, as inputs may well start with a `<`
+	let head = start
+		.chars()
+		.take(16)
+		.collect::<String>()
+		.to_ascii_lowercase();
+	if head.starts_with("<!doctype html") || head.starts_with("<html") {
+		return Err(InputError::Html);
+	}
+	if start.starts_with("Puzzle inputs differ by user") {
+		return Err(InputError::LoggedOut);
+	}
+	if start.starts_with("Please don't repeatedly request this endpoint") {
+		return Err(InputError::RateLimited);
+	}
+
+	Ok(input)
+}
+
+/// Writes `input` to `input.txt` in `dir` with its checksum next to it. An existing input with
+/// different content is moved to `input.txt.bak` with a warning.
+pub fn save(changes: &Changes, dir: &Path, input: &str) -> anyhow::Result<()> {
+	let path = dir.join(INPUT_FILE);
+	if let Ok(old) = changes.read_to_string(&path) {
+		if old != input {
+			let backup = dir.join(BACKUP_FILE);
+			changes
+				.rename(&path, &backup)
+				.with_context(|| format!("failed to back up {}", path.display()))?;
+			eprintln!(
+				"Warning: the downloaded input differs from {}, the old one was moved to {}",
+				path.display(),
+				backup.display()
+			);
+		}
+	}
+
+	changes.write(&path, input)?;
+	changes.write(
+		&dir.join(CHECKSUM_FILE),
+		&format!("{}  {}\n", sha256_hex(input), INPUT_FILE),
+	)
+}
+
+fn sha256_hex(input: &str) -> String {
+	Sha256::digest(input.as_bytes())
+		.iter()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::InputError;
+	use crate::changes::Changes;
+	use std::fs;
+
+	#[test]
+	fn check() {
+		assert_eq!(
+			super::check("1abc2\r\npqr3stu8vwx\r\n").unwrap(),
+			"1abc2\npqr3stu8vwx\n"
+		);
+		assert_eq!(
+			super::check("<{([{{}}[<[[[<>{}]]]>[]]\n").unwrap(),
+			"<{([{{}}[<[[[<>{}]]]>[]]\n"
+		);
+
+		assert_eq!(super::check(""), Err(InputError::Empty));
+		assert_eq!(super::check(" \n"), Err(InputError::Empty));
+		assert_eq!(
+			super::check("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+			Err(InputError::Html)
+		);
+		assert_eq!(
+			super::check(
+				"Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+			),
+			Err(InputError::LoggedOut)
+		);
+		assert_eq!(
+			super::check("Please don't repeatedly request this endpoint before it unlocks!\n"),
+			Err(InputError::RateLimited)
+		);
+	}
+
+	#[test]
+	fn save() {
+		let dir = tempfile::tempdir().unwrap();
+		let save = |input| super::save(&Changes::default(), dir.path(), input);
+
+		save("abc").unwrap();
+		assert_eq!(
+			fs::read_to_string(dir.path().join(super::CHECKSUM_FILE)).unwrap(),
+			"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  input.txt\n"
+		);
+
+		save("abc").unwrap();
+		assert!(!dir.path().join(super::BACKUP_FILE).exists());
+
+		save("abd").unwrap();
+		assert_eq!(
+			fs::read_to_string(dir.path().join(super::BACKUP_FILE)).unwrap(),
+			"abc"
+		);
+		assert_eq!(
+			fs::read_to_string(dir.path().join(super::INPUT_FILE)).unwrap(),
+			"abd"
+		);
+	}
+}
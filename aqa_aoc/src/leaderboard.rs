@@ -0,0 +1,285 @@
+//! Private leaderboards, from `/{year}/leaderboard/private/view/{id}.json`
+
+use crate::client::AocClient;
+use crate::table::{Align, Table};
+use crate::unlock;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+	pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+	pub id: u64,
+	/// Missing for anonymous users
+	pub name: Option<String>,
+	pub stars: u32,
+	pub local_score: u64,
+	/// Unix time of the last star, 0 without stars
+	pub last_star_ts: u64,
+	/// Stars by day and part
+	#[serde(default)]
+	pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+	pub get_star_ts: u64,
+}
+
+impl Member {
+	fn display_name(&self) -> String {
+		match &self.name {
+			Some(name) => name.clone(),
+			None => format!("(anonymous user #{})", self.id),
+		}
+	}
+
+	/// Time from the unlock of the day to getting the star of `part`
+	fn solve_time(&self, year: u32, day: u32, part: u32) -> Option<Duration> {
+		let star = self.completion_day_level.get(&day)?.get(&part)?;
+		(UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+			.duration_since(unlock::unlock_time(year, day))
+			.ok()
+	}
+}
+
+/// Prints the leaderboard, or its JSON as the site sent it with `json`
+pub fn print_leaderboard(
+	client: &AocClient,
+	year: u32,
+	id: u64,
+	day: Option<u32>,
+	json: bool,
+) -> anyhow::Result<()> {
+	let body = client.leaderboard(year, id)?;
+	if json {
+		println!("{}", body.trim_end());
+		return Ok(());
+	}
+
+	let leaderboard = parse(&body, id)?;
+	print!("{}", render(&leaderboard, year, day));
+	Ok(())
+}
+
+/// Parses the JSON of leaderboard `id`. The site sends a page instead when the session can't view
+/// the leaderboard.
+pub fn parse(body: &str, id: u64) -> anyhow::Result<Leaderboard> {
+	serde_json::from_str(body).map_err(|err| {
+		anyhow::anyhow!(
+			"failed to parse leaderboard {}, make sure you can view it: {}",
+			id,
+			err
+		)
+	})
+}
+
+/// Renders the ranking and the solve times of `day`, or of every day up to the last one anyone has
+/// a star for
+pub fn render(leaderboard: &Leaderboard, year: u32, day: Option<u32>) -> String {
+	let mut members: Vec<&Member> = leaderboard.members.values().collect();
+	members.sort_by_key(|member| {
+		(
+			std::cmp::Reverse(member.local_score),
+			std::cmp::Reverse(member.stars),
+			member.last_star_ts,
+			member.id,
+		)
+	});
+	let last_day = members
+		.iter()
+		.filter_map(|member| member.completion_day_level.keys().next_back())
+		.max()
+		.copied()
+		.unwrap_or(0);
+
+	let mut ranking = Table::new([
+		("Rank", Align::Right),
+		("Score", Align::Right),
+		("Stars", Align::Left),
+		("Name", Align::Left),
+	]);
+	for (idx, member) in members.iter().enumerate() {
+		let stars: String = (1..=last_day)
+			.map(
+				|day| match member.completion_day_level.get(&day).map(BTreeMap::len) {
+					Some(2) => '*',
+					Some(1) => '+',
+					_ => '.',
+				},
+			)
+			.collect();
+		ranking.push_row(vec![
+			(idx + 1).to_string(),
+			member.local_score.to_string(),
+			stars,
+			member.display_name(),
+		]);
+	}
+
+	let mut out = ranking.to_string();
+	out.push_str("* both parts, + part 1 only\n");
+
+	let day = match day {
+		Some(day) => day,
+		None => {
+			if last_day > 0 {
+				let _ = write!(
+					out,
+					"\nTime since unlock, part 1 / part 2\n{}",
+					all_days_table(&members, year, last_day)
+				);
+			}
+			return out;
+		}
+	};
+	let mut times = Table::new([
+		("Name", Align::Left),
+		("Part 1", Align::Right),
+		("Part 2", Align::Right),
+	]);
+	let mut solvers: Vec<(Option<Duration>, Option<Duration>, &Member)> = members
+		.iter()
+		.map(|member| {
+			(
+				member.solve_time(year, day, 1),
+				member.solve_time(year, day, 2),
+				*member,
+			)
+		})
+		.filter(|(part_1, _, _)| part_1.is_some())
+		.collect();
+	// Part 2 solvers first, then by time, with None sorting last
+	solvers.sort_by_key(|(part_1, part_2, _)| (part_2.is_none(), *part_2, *part_1));
+	for (part_1, part_2, member) in solvers {
+		times.push_row(vec![
+			member.display_name(),
+			format_solve_time(part_1),
+			format_solve_time(part_2),
+		]);
+	}
+
+	let _ = write!(out, "\nDay {}, time since unlock\n{}", day, times);
+	out
+}
+
+/// Solve times of `members`, in ranking order, for days 1 to `last_day`
+fn all_days_table(members: &[&Member], year: u32, last_day: u32) -> Table {
+	let headers: Vec<String> = (1..=last_day).map(|day| format!("Day {}", day)).collect();
+	let mut table = Table::new(
+		std::iter::once(("Name", Align::Left))
+			.chain(headers.iter().map(|header| (header.as_str(), Align::Right))),
+	);
+	for member in members {
+		let mut row = vec![member.display_name()];
+		row.extend((1..=last_day).map(|day| {
+			match (
+				member.solve_time(year, day, 1),
+				member.solve_time(year, day, 2),
+			) {
+				(None, _) => String::from("-"),
+				(part_1, part_2) => format!(
+					"{} / {}",
+					format_solve_time(part_1),
+					format_solve_time(part_2)
+				),
+			}
+		}));
+		table.push_row(row);
+	}
+	table
+}
+
+fn format_solve_time(time: Option<Duration>) -> String {
+	time.map_or(String::from("-"), unlock::format_countdown)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Leaderboard;
+	use crate::client::test_client;
+	use crate::fixture_server::FixtureServer;
+
+	// 2023-12-01T05:00:00Z is 1701406800
+	const LEADERBOARD: &str = r#"{
+	"event": "2023",
+	"owner_id": 1,
+	"members": {
+		"1": {
+			"id": 1, "name": "aQaTL", "stars": 3, "local_score": 8, "global_score": 0,
+			"last_star_ts": 1701494000,
+			"completion_day_level": {
+				"1": {
+					"1": {"get_star_ts": 1701407400, "star_index": 1},
+					"2": {"get_star_ts": 1701410461, "star_index": 2}
+				},
+				"2": {"1": {"get_star_ts": 1701494000, "star_index": 5}}
+			}
+		},
+		"2": {
+			"id": 2, "name": null, "stars": 2, "local_score": 4, "global_score": 0,
+			"last_star_ts": 1701408000,
+			"completion_day_level": {
+				"1": {
+					"1": {"get_star_ts": 1701407000, "star_index": 0},
+					"2": {"get_star_ts": 1701408000, "star_index": 3}
+				}
+			}
+		},
+		"3": {
+			"id": 3, "name": "lurker", "stars": 0, "local_score": 0, "global_score": 0,
+			"last_star_ts": 0, "completion_day_level": {}
+		}
+	}
+}"#;
+
+	#[test]
+	fn render() {
+		let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+
+		assert_eq!(
+			super::render(&leaderboard, 2023, None),
+			"\
+Rank  Score  Stars  Name
+   1      8  *+     aQaTL
+   2      4  *.     (anonymous user #2)
+   3      0  ..     lurker
+* both parts, + part 1 only
+
+Time since unlock, part 1 / part 2
+Name                               Day 1         Day 2
+aQaTL                00:10:00 / 01:01:01  00:13:20 / -
+(anonymous user #2)  00:03:20 / 00:20:00             -
+lurker                                 -             -
+"
+		);
+		assert!(super::render(&leaderboard, 2023, Some(1)).ends_with(
+			"\
+Day 1, time since unlock
+Name                   Part 1    Part 2
+(anonymous user #2)  00:03:20  00:20:00
+aQaTL                00:10:00  01:01:01
+"
+		));
+	}
+
+	#[test]
+	fn fetch_from_server() {
+		let server = FixtureServer::start(|_request| (200, LEADERBOARD.to_string())).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		for _ in 0..2 {
+			assert_eq!(client.leaderboard(2023, 1).unwrap(), LEADERBOARD);
+		}
+		let requests = server.requests();
+		assert_eq!(requests.len(), 1);
+		assert_eq!(requests[0].path, "/2023/leaderboard/private/view/1.json");
+	}
+}
@@ -1,18 +1,90 @@
+mod answers;
+mod calendar;
+mod changes;
+mod cli;
+mod client;
+mod config;
+mod day_crate;
+mod discovery;
+mod examples;
+#[cfg(test)]
+mod fixture_server;
+mod input;
+mod leaderboard;
+mod manifest;
+mod puzzle;
+mod runner;
+mod scaffold;
+mod session;
+mod status;
+mod submit;
+mod table;
+mod unlock;
+
 use anyhow::Context;
+use changes::Changes;
+use clap::Parser;
+use day_crate::{DayCrate, Layout};
 use serde::Deserialize;
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 use thiserror::Error;
-use ureq::Cookie;
 
 static AOC_ADDR: &str = "https://adventofcode.com";
 
 #[derive(Deserialize)]
 struct AocCfg {
 	year: u32,
-	session_cookie: String,
+	/// Overridden by the `AOC_SESSION` environment variable and `session_cookie_file`
+	session_cookie: Option<String>,
+	/// File holding the session cookie, relative to the config file
+	session_cookie_file: Option<PathBuf>,
+	/// Address of the Advent of Code site, overridable to talk to a local fixture server
+	base_url: Option<String>,
+	#[serde(default)]
+	scaffold: scaffold::ScaffoldCfg,
+	/// Whether day crates are `day_N` or `YYYY/day_N`
+	#[serde(default)]
+	layout: Layout,
+	/// Seconds to wait between requests to the site, `client::MIN_REQUEST_INTERVAL` by default
+	min_request_interval: Option<f64>,
+	/// Directory of the config file
+	#[serde(skip)]
+	dir: PathBuf,
+}
+
+impl AocCfg {
+	fn base_url(&self) -> &str {
+		self.base_url
+			.as_deref()
+			.unwrap_or(AOC_ADDR)
+			.trim_end_matches('/')
+	}
+
+	fn session_cookie(&self) -> anyhow::Result<String> {
+		config::session_cookie(
+			std::env::var(config::SESSION_ENV).ok(),
+			self.session_cookie_file.as_deref(),
+			self.session_cookie.as_deref(),
+			&self.dir,
+		)
+	}
+
+	fn client(&self) -> anyhow::Result<client::AocClient> {
+		let client = client::AocClient::new(
+			self.base_url(),
+			&self.session_cookie()?,
+			&client::default_cache_dir()?,
+		);
+		Ok(match self.min_request_interval {
+			Some(secs) => client.min_interval(
+				Duration::try_from_secs_f64(secs)
+					.context("invalid min_request_interval in the config")?,
+			),
+			None => client,
+		})
+	}
 }
 
 #[derive(Debug, Error)]
@@ -25,261 +97,472 @@ pub enum CargoTomlParserError {
 	FailedToGetDependencies,
 }
 
-#[derive(Debug)]
-struct CliApp {
-	day: Option<u32>,
+#[derive(Debug, Error)]
+pub enum CliError {
+	#[error("submit requires --day")]
+	MissingDay,
+	#[error("--dry-run only applies to fetch, new and submit, the other commands change nothing")]
+	DryRunUnsupported,
 }
 
-impl CliApp {
-	fn from_args() -> anyhow::Result<CliApp> {
-		let mut day = None;
+fn main() -> anyhow::Result<()> {
+	let cli = cli::Cli::parse();
+	let start = std::env::current_dir()?;
+	let config_path = cli.config.as_ref().map(|path| start.join(path));
+	// Paths like Cargo.toml, answers.toml and day_N are relative to the workspace root
+	let found = match &config_path {
+		Some(path) => Some(path.clone()),
+		None => config::find(&start).ok(),
+	};
+	if let Some(root) = config::workspace_root(&start, found.as_deref()) {
+		std::env::set_current_dir(&root)
+			.with_context(|| format!("failed to change directory to {}", root.display()))?;
+	}
+	let load_cfg = || -> anyhow::Result<AocCfg> {
+		let path = match &config_path {
+			Some(path) => path.clone(),
+			None => config::find(&start)?,
+		};
+		let mut cfg = load_cfg(&path)?;
+		if let Some(year) = cli.year {
+			cfg.year = year;
+		}
+		if cli.base_url.is_some() {
+			cfg.base_url = cli.base_url.clone();
+		}
+		Ok(cfg)
+	};
 
-		for arg in std::env::args().skip(1) {
-			if let Some(arg) = arg.strip_prefix("--day=") {
-				day = Some(arg.parse::<u32>()?);
+	// Running, verifying and the offline status work without a config, the layout only tells
+	// which year flat crates belong to
+	let layout = || -> anyhow::Result<Layout> {
+		match load_cfg() {
+			Ok(cfg) => Ok(cfg.layout),
+			Err(err)
+				if matches!(
+					err.downcast_ref::<config::ConfigError>(),
+					Some(config::ConfigError::NotFound { .. })
+				) =>
+			{
+				Ok(Layout::default())
 			}
+			Err(err) => Err(err),
 		}
+	};
 
-		Ok(CliApp { day })
+	let changes = Changes::new(cli.dry_run);
+
+	let command = cli.command.as_ref().unwrap_or(&cli::Command::Fetch {
+		force: false,
+		wait: false,
+	});
+	let changes_files = matches!(
+		command,
+		cli::Command::Fetch { .. } | cli::Command::New | cli::Command::Submit { .. }
+	);
+	if cli.dry_run && !changes_files {
+		return Err(CliError::DryRunUnsupported.into());
+	}
+
+	match command {
+		cli::Command::Fetch { force, wait } => {
+			fetch(cli.day, *force, *wait, &load_cfg()?, &changes)
+		}
+		cli::Command::New => new(cli.day, &load_cfg()?, &changes),
+		cli::Command::Submit { part, answer } => {
+			let cfg = load_cfg()?;
+			let day = cli.day.ok_or(CliError::MissingDay)?;
+			let client = cfg.client()?;
+			let day_crate = DayCrate::new(cfg.layout, cfg.year, day);
+			if changes.is_dry_run() {
+				// The answer would be recorded in an answers.toml that has to make sense
+				answers::Answers::load(Path::new(answers::ANSWERS_FILE))?
+					.set(&day_crate, *part, answer)?;
+				println!(
+					"Would send POST {} with level={} and answer={}",
+					client.url(&format!("/{}/day/{}/answer", cfg.year, day)),
+					part,
+					answer
+				);
+				println!(
+					"Would record the answer in {} if it's right",
+					answers::ANSWERS_FILE
+				);
+				return Ok(());
+			}
+			println!(
+				"Submitting \"{}\" as the answer for day {} part {}",
+				answer, day, part
+			);
+			let verdict = submit::submit_answer(&client, cfg.year, day, *part, answer)?;
+			println!("{}", verdict);
+			if verdict == submit::Verdict::Correct {
+				answers::record(&changes, &day_crate, *part, answer)?;
+			}
+			Ok(())
+		}
+		// Running the solutions needs neither the site nor the session cookie
+		cli::Command::Run { part, timeout } => runner::run(
+			cli.year,
+			layout()?,
+			cli.day,
+			*part,
+			Duration::from_secs(*timeout),
+		),
+		cli::Command::Verify { timeout } => {
+			answers::verify(cli.year, layout()?, cli.day, Duration::from_secs(*timeout))
+		}
+		cli::Command::Status { offline } => {
+			let calendar = if *offline {
+				None
+			} else {
+				let cfg = load_cfg()?;
+				Some(status::Calendar {
+					year: cfg.year,
+					layout: cfg.layout,
+					stars: calendar::get_stars(&cfg.client()?, cfg.year)?,
+				})
+			};
+			status::print_status(cli.year, layout()?, cli.day, calendar.as_ref())
+		}
+		cli::Command::Open => {
+			let cfg = load_cfg()?;
+			let url = match cli.day {
+				Some(day) => format!("{}/{}/day/{}", cfg.base_url(), cfg.year, day),
+				None => format!("{}/{}", cfg.base_url(), cfg.year),
+			};
+			println!("Opening {}", url);
+			open_in_browser(&url)
+		}
+		cli::Command::Leaderboard { id, json } => {
+			let cfg = load_cfg()?;
+			leaderboard::print_leaderboard(&cfg.client()?, cfg.year, *id, cli.day, *json)
+		}
+		cli::Command::Whoami => {
+			let cfg = load_cfg()?;
+			match session::whoami(&cfg.client()?)? {
+				Some(user) => println!("Logged in as {}", user),
+				None => anyhow::bail!("the session cookie is invalid or expired"),
+			}
+			Ok(())
+		}
+		cli::Command::Completions { shell } => {
+			cli::print_completions(*shell);
+			Ok(())
+		}
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let cli = CliApp::from_args()?;
+fn load_cfg(path: &Path) -> anyhow::Result<AocCfg> {
+	let mut cfg: AocCfg = toml::from_slice(
+		&std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+	)
+	.with_context(|| format!("failed to parse {}", path.display()))?;
+	cfg.dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+	Ok(cfg)
+}
 
-	let cfg: AocCfg =
-		toml::from_slice(&std::fs::read("aoc_cfg.toml").context("failed to read aoc_cfg.toml")?)
-			.context("failed to parse aoc_cfg.toml")?;
+/// Creates the crate of `day`, or of the day after the last one, without downloading anything
+fn new(day: Option<u32>, cfg: &AocCfg, changes: &Changes) -> anyhow::Result<()> {
+	let mut cargo_workspace = manifest::read(Path::new("Cargo.toml"))?;
 
-	let mut cargo_workspace: toml::Value =
-		toml::from_slice(&std::fs::read("Cargo.toml").context("failed to read Cargo.toml")?)
-			.context("failed to parse Cargo.toml")?;
+	let day = match day {
+		Some(specific_day) => {
+			work_with_specific_day(&mut cargo_workspace, specific_day, cfg, changes)?
+		}
+		None => {
+			let days = discovery::scan(&cargo_workspace, Path::new("."), cfg.layout, cfg.year)?;
+			let day = discovery::next_day(&days);
+			work_with_day(&mut cargo_workspace, &days, day, cfg, changes)?
+		}
+	};
+
+	match day {
+		Day::NewDay(day_crate) => add_new_day(&mut cargo_workspace, day_crate, cfg, changes),
+		Day::CreatedBefore(day_crate) => {
+			println!("{} already exists", day_crate.member());
+			Ok(())
+		}
+	}
+}
+
+/// Attempts at downloading a day right after it unlocks
+const UNLOCK_ATTEMPTS: u32 = 6;
 
-	let day = match cli.day {
-		Some(specific_day) => work_with_specific_day(&mut cargo_workspace, specific_day)?,
-		None => get_day_we_are_working_with(&mut cargo_workspace)?,
+fn fetch(
+	day: Option<u32>,
+	force: bool,
+	wait: bool,
+	cfg: &AocCfg,
+	changes: &Changes,
+) -> anyhow::Result<()> {
+	let client = cfg.client()?;
+	let mut cargo_workspace = manifest::read(Path::new("Cargo.toml"))?;
+
+	let day = match day {
+		Some(specific_day) => {
+			work_with_specific_day(&mut cargo_workspace, specific_day, cfg, changes)?
+		}
+		None => get_day_we_are_working_with(&mut cargo_workspace, cfg, changes)?,
 	};
 
 	println!("Working with day: {}", day.get_day());
 
-	let day_crate_name = day.get_crate_name();
+	if !wait {
+		if let Some(left) =
+			unlock::time_to_unlock(cfg.year, day.get_day(), std::time::SystemTime::now())
+		{
+			anyhow::bail!(
+				"day {} of {} unlocks in {}, use --wait to download it then",
+				day.get_day(),
+				cfg.year,
+				unlock::format_countdown(left)
+			);
+		}
+	}
+
+	let day_crate_name = day.day_crate().member();
+
+	if let Day::NewDay(day_crate) = day {
+		add_new_day(&mut cargo_workspace, day_crate, cfg, changes)?;
+	}
+
+	let crate_dir = day.day_crate().dir();
+	let input_path = crate_dir.join(input::INPUT_FILE);
+	let puzzle_path = crate_dir.join("puzzle.md");
+
+	if changes.is_dry_run() {
+		let input_url = client.url(&format!("/{}/day/{}/input", cfg.year, day.get_day()));
+		let puzzle_url = client.url(&format!("/{}/day/{}", cfg.year, day.get_day()));
+		if let Some(left) =
+			unlock::time_to_unlock(cfg.year, day.get_day(), std::time::SystemTime::now())
+		{
+			println!(
+				"Would wait {} for day {} to unlock",
+				unlock::format_countdown(left),
+				day.get_day()
+			);
+		}
+		if changes.exists(&input_path) && !force {
+			println!("{} already exists", input_path.display());
+		} else {
+			if client.is_input_cached(cfg.year, day.get_day()) && !force {
+				println!("Would use the cached input instead of GET {}", input_url);
+			} else {
+				println!("Would send GET {}", input_url);
+			}
+			println!(
+				"Would save the input to {} with its checksum in {}",
+				input_path.display(),
+				crate_dir.join(input::CHECKSUM_FILE).display()
+			);
+		}
+		println!("Would send GET {}", puzzle_url);
+		println!(
+			"Would write {} and the examples with their tests from the puzzle page",
+			puzzle_path.display()
+		);
+		return Ok(());
+	}
+
+	// Retrying only makes sense while the site is flooded right after the unlock
+	let attempts = if wait {
+		unlock::wait_for_unlock(cfg.year, day.get_day());
+		UNLOCK_ATTEMPTS
+	} else {
+		1
+	};
+	let retry_delay = Duration::from_secs(1);
+
+	if input_path.exists() && !force {
+		println!(
+			"{}/input.txt already exists, use --force to download it again",
+			day_crate_name
+		);
+	} else {
+		println!("Downloading day {} input...", day.get_day());
 
-	if let Day::NewDay(day) = day {
-		println!("Adding \"day_{}\" to workspace members", day);
+		let input = client::retry(attempts, retry_delay, || {
+			client.input(cfg.year, day.get_day(), force)
+		})?;
 
-		let mut cargo_workspace_file = File::create("Cargo.toml")?;
-		let cargo_workspace_str = toml::to_string_pretty(&cargo_workspace)?;
-		cargo_workspace_file.write_all(cargo_workspace_str.as_bytes())?;
+		input::save(changes, &crate_dir, &input)?;
 
-		create_crate_for_new_day(&day_crate_name)?;
+		println!("Input saved to {}/input.txt", day_crate_name);
 	}
 
-	std::env::set_current_dir(&day_crate_name)?;
+	println!("Downloading day {} puzzle...", day.get_day());
 
-	println!("Downloading day {} input...", day.get_day());
+	let puzzle_page = client::retry(attempts, retry_delay, || {
+		puzzle::get_puzzle_page(&client, cfg.year, day.get_day())
+	})?;
+	let puzzle_url = format!("{}/{}/day/{}", cfg.base_url(), cfg.year, day.get_day());
+	let puzzle = puzzle::puzzle_to_markdown(&puzzle_page, &puzzle_url)?;
 
-	let day_1_input = get_input(cfg.year, day.get_day(), &cfg.session_cookie)?;
+	changes.write(&puzzle_path, &puzzle)?;
 
-	let mut input_file = File::create("input.txt")?;
-	input_file.write_all(day_1_input.as_bytes())?;
+	println!("Puzzle saved to {}/puzzle.md", day_crate_name);
 
-	println!("Input saved to {}/input.txt", day_crate_name);
+	let examples = examples::extract_examples(&puzzle_page);
+	examples::write_examples(changes, &crate_dir, &examples)?;
+
+	println!(
+		"{} example(s) saved to {}/examples",
+		examples.len(),
+		day_crate_name
+	);
 
 	Ok(())
 }
 
 #[derive(Copy, Clone)]
 enum Day {
-	NewDay(u32),
-	CreatedBefore(u32),
+	NewDay(DayCrate),
+	CreatedBefore(DayCrate),
 }
 
 impl Day {
-	fn get_day(&self) -> u32 {
+	fn day_crate(&self) -> DayCrate {
 		match self {
-			Day::NewDay(day) | Day::CreatedBefore(day) => *day,
+			Day::NewDay(day_crate) | Day::CreatedBefore(day_crate) => *day_crate,
 		}
 	}
 
-	fn get_crate_name(&self) -> String {
-		format!("day_{}", self.get_day())
+	fn get_day(&self) -> u32 {
+		self.day_crate().day
 	}
 }
 
-fn get_day_we_are_working_with(cargo_workspace: &mut toml::Value) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut last_day_without_input = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		let day = match day {
-			Some(v) => v,
-			None => continue,
-		};
-
-		if !dir.path().join("input.txt").exists() {
-			last_day_without_input = Some(day);
-			break;
-		}
-	}
-
-	match last_day_without_input {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			let day = members
-				.iter()
-				.filter_map(toml::Value::as_str)
-				.filter_map(|str| str.strip_prefix("day_"))
-				.filter_map(|day| day.parse::<u32>().ok())
-				.max()
-				.unwrap_or(0) + 1;
-
-			members.push(toml::Value::String(format!("day_{}", day)));
-			Ok(Day::NewDay(day))
-		}
-	}
+fn get_day_we_are_working_with(
+	cargo_workspace: &mut toml_edit::DocumentMut,
+	cfg: &AocCfg,
+	changes: &Changes,
+) -> anyhow::Result<Day> {
+	let days = discovery::scan(cargo_workspace, Path::new("."), cfg.layout, cfg.year)?;
+	let day = discovery::first_unfinished(&days).unwrap_or_else(|| discovery::next_day(&days));
+	work_with_day(cargo_workspace, &days, day, cfg, changes)
 }
 
 fn work_with_specific_day(
-	cargo_workspace: &mut toml::Value,
+	cargo_workspace: &mut toml_edit::DocumentMut,
 	specific_day: u32,
+	cfg: &AocCfg,
+	changes: &Changes,
 ) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut day_project = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		match day {
-			Some(day) if day == specific_day => {
-				day_project = Some(day);
-				break;
-			}
-			_ => (),
-		};
+	let days = discovery::scan(cargo_workspace, Path::new("."), cfg.layout, cfg.year)?;
+	work_with_day(cargo_workspace, &days, specific_day, cfg, changes)
+}
+
+/// Makes `day` a workspace member if it isn't one. Its crate still has to be created if it's a
+/// [`Day::NewDay`].
+fn work_with_day(
+	cargo_workspace: &mut toml_edit::DocumentMut,
+	days: &[discovery::DayState],
+	day: u32,
+	cfg: &AocCfg,
+	changes: &Changes,
+) -> anyhow::Result<Day> {
+	let state = days.iter().find(|state| state.day_crate.day == day);
+	let day_crate = state.map_or(DayCrate::new(cfg.layout, cfg.year, day), |state| {
+		state.day_crate
+	});
+	let is_member = state.is_some_and(|state| state.is_member);
+	if !is_member {
+		manifest::insert_workspace_member(cargo_workspace, &day_crate.member())?;
 	}
 
-	match day_project {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			members.push(toml::Value::String(format!("day_{}", specific_day)));
-			Ok(Day::NewDay(specific_day))
-		}
+	if !state.is_some_and(|state| state.has_dir) {
+		return Ok(Day::NewDay(day_crate));
+	}
+	// A crate directory someone forgot to list in the workspace
+	if !is_member {
+		println!("Adding \"{}\" to workspace members", day_crate.member());
+		manifest::write(changes, Path::new("Cargo.toml"), cargo_workspace)?;
 	}
+	Ok(Day::CreatedBefore(day_crate))
+}
+
+/// Writes the workspace manifest with the new member added and creates its crate
+fn add_new_day(
+	cargo_workspace: &mut toml_edit::DocumentMut,
+	day_crate: DayCrate,
+	cfg: &AocCfg,
+	changes: &Changes,
+) -> anyhow::Result<()> {
+	println!("Adding \"{}\" to workspace members", day_crate.member());
+
+	manifest::write(changes, Path::new("Cargo.toml"), cargo_workspace)?;
+
+	create_crate_for_new_day(day_crate, cfg, changes)
 }
 
-fn create_crate_for_new_day(new_crate_name: &str) -> anyhow::Result<()> {
+fn create_crate_for_new_day(
+	day_crate: DayCrate,
+	cfg: &AocCfg,
+	changes: &Changes,
+) -> anyhow::Result<()> {
+	let crate_dir = day_crate.dir();
+	let new_crate_name = day_crate.package_name();
 	println!("Creating new crate (\"{}\")", new_crate_name);
 
-	let cargo_new_status = Command::new("cargo")
-		.args(["new", new_crate_name])
-		.status()?;
-	if !cargo_new_status.success() {
-		return Err(anyhow::anyhow!(
-			"cargo new failed with code {}",
-			cargo_new_status.code().unwrap_or_default()
-		));
+	if let Some(template) = &cfg.scaffold.template {
+		println!("Using template {}", template.display());
 	}
-
-	add_useful_deps(new_crate_name)?;
+	scaffold::create_crate(
+		changes,
+		&crate_dir,
+		cfg.scaffold.template.as_deref(),
+		&scaffold::TemplateVars {
+			day: day_crate.day,
+			year: cfg.year,
+			crate_name: &new_crate_name,
+			workspace_root: &scaffold::workspace_root(&crate_dir),
+		},
+	)?;
+
+	add_useful_deps(&crate_dir, cfg, changes)?;
 
 	Ok(())
 }
 
-fn add_useful_deps(new_crate_name: &str) -> anyhow::Result<()> {
-	let cargo_toml_path = Path::new(new_crate_name).join("Cargo.toml");
-	let mut cargo_toml: toml::Value = toml::from_slice(
-		&std::fs::read(&cargo_toml_path)
-			.with_context(|| format!("failed to read {}", cargo_toml_path.display()))?,
-	)
-	.with_context(|| format!("failed to parse {}", cargo_toml_path.display()))?;
-
-	let dependencies = cargo_toml
-		.get_mut("dependencies")
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?
-		.as_table_mut()
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
-
-	dependencies.insert("anyhow".to_string(), toml::Value::String("1.0".to_string()));
-	dependencies.insert(
-		"itertools".to_string(),
-		toml::Value::String("0.10".to_string()),
-	);
-	dependencies.insert("regex".to_string(), toml::Value::String("1.4".to_string()));
-	dependencies.insert("nom".to_string(), toml::Value::String("7.0.0".to_string()));
+fn add_useful_deps(crate_dir: &Path, cfg: &AocCfg, changes: &Changes) -> anyhow::Result<()> {
+	let cargo_toml_path = crate_dir.join("Cargo.toml");
+	let mut cargo_toml = manifest::read_planned(changes, &cargo_toml_path)?;
+
+	let dependencies = cfg.scaffold.dependencies();
+	manifest::add_dependencies(
+		&mut cargo_toml,
+		dependencies
+			.iter()
+			.map(|(name, dep)| (name.as_str(), dep.to_toml(crate_dir))),
+	)?;
 
-	let mut cargo_toml_file = File::create(&cargo_toml_path)?;
-	let cargo_toml_str = toml::to_string_pretty(&cargo_toml)?;
-	cargo_toml_file.write_all(cargo_toml_str.as_bytes())?;
+	manifest::write(changes, &cargo_toml_path, &cargo_toml)?;
 
-	println!("[dependencies] updated");
+	if !changes.is_dry_run() {
+		println!("[dependencies] updated");
+	}
 
 	Ok(())
 }
 
-fn get_input(year: u32, day: u32, cookie: &str) -> anyhow::Result<String> {
-	let cookie = Cookie::build("session", cookie)
-		.domain("adventofcode.com")
-		.path("/")
-		.secure(true)
-		.finish()
-		.into_owned();
-
-	let agent = ureq::agent();
-	agent.set_cookie(cookie);
-
-	let req_adr = format!(
-		"{addr}/{year}/day/{day}/input",
-		addr = AOC_ADDR,
-		year = year,
-		day = day
-	);
-	println!("Request addr: {}", req_adr);
-	let mut request = agent.get(&req_adr);
-	let response = request.call();
-
-	if response.synthetic() {
-		// SAFETY: synthetic returns true only when error is Some
-		return Err(response.into_synthetic_error().unwrap().into());
-	}
+fn open_in_browser(url: &str) -> anyhow::Result<()> {
+	let mut command = if cfg!(target_os = "windows") {
+		let mut command = Command::new("cmd");
+		command.args(["/C", "start", ""]);
+		command
+	} else if cfg!(target_os = "macos") {
+		Command::new("open")
+	} else {
+		Command::new("xdg-open")
+	};
 
-	if response.error() {
-		return Err(anyhow::anyhow!(
-			"Failed to fetch input: {}",
-			response.status_line()
-		));
+	let status = command
+		.arg(url)
+		.status()
+		.context("failed to start the browser")?;
+	if !status.success() {
+		anyhow::bail!("failed to open {}: {}", url, status);
 	}
-
-	response.into_string().map_err(Into::into)
+	Ok(())
 }
@@ -0,0 +1,268 @@
+//! Cargo.toml edits that keep the formatting, comments and quote style of the file
+
+use crate::changes::Changes;
+use crate::day_crate::DayCrate;
+use crate::CargoTomlParserError;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Value};
+
+pub fn read(path: &Path) -> anyhow::Result<DocumentMut> {
+	read_planned(&Changes::default(), path)
+}
+
+/// Reads the manifest as it would be after `changes`, for one written during a dry run
+pub fn read_planned(changes: &Changes, path: &Path) -> anyhow::Result<DocumentMut> {
+	use anyhow::Context;
+
+	changes
+		.read_to_string(path)?
+		.parse::<DocumentMut>()
+		.with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn write(changes: &Changes, path: &Path, manifest: &DocumentMut) -> anyhow::Result<()> {
+	changes.write(path, &manifest.to_string())
+}
+
+pub fn workspace_members(manifest: &DocumentMut) -> anyhow::Result<Vec<String>> {
+	let members = manifest
+		.get("workspace")
+		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+		.get("members")
+		.and_then(Item::as_array)
+		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
+
+	Ok(members
+		.iter()
+		.filter_map(Value::as_str)
+		.map(String::from)
+		.collect())
+}
+
+/// Inserts `member` into `workspace.members`, before the first day crate with a greater year and
+/// day, so `day_10` lands after `day_9` and not after `day_1`
+pub fn insert_workspace_member(manifest: &mut DocumentMut, member: &str) -> anyhow::Result<()> {
+	let members = manifest
+		.get_mut("workspace")
+		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+		.get_mut("members")
+		.and_then(Item::as_array_mut)
+		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
+
+	if members.iter().any(|m| m.as_str() == Some(member)) {
+		return Ok(());
+	}
+
+	let day_crate = DayCrate::from_member(member);
+	let idx = members
+		.iter()
+		.position(
+			|m| match (day_crate, m.as_str().and_then(DayCrate::from_member)) {
+				(Some(day_crate), Some(m_day_crate)) => m_day_crate > day_crate,
+				_ => false,
+			},
+		)
+		.unwrap_or(members.len());
+
+	// Format the new member like its neighbour
+	let neighbour = members
+		.get(idx)
+		.or_else(|| members.get(idx.wrapping_sub(1)));
+	let mut value = match neighbour {
+		Some(Value::String(neighbour)) if neighbour.display_repr().starts_with('\'') => {
+			format!("'{}'", member).parse::<Value>()?
+		}
+		_ => Value::from(member),
+	};
+
+	// The prefix of a member also holds comments following the previous member, which must stay
+	// where they are. Only the indentation is shared.
+	let prefix = neighbour
+		.and_then(|n| n.decor().prefix())
+		.and_then(|p| p.as_str())
+		.unwrap_or_default()
+		.to_string();
+	let indent = match prefix.rfind('\n') {
+		Some(newline) => prefix[newline..].to_string(),
+		None => prefix.clone(),
+	};
+	if let Some(next) = members.get_mut(idx) {
+		next.decor_mut().set_prefix(indent);
+		value.decor_mut().set_prefix(prefix);
+	} else if idx > 0 {
+		value.decor_mut().set_prefix(indent);
+	} else if members
+		.trailing()
+		.as_str()
+		.is_some_and(|t| t.contains('\n'))
+	{
+		// Empty multi-line array
+		value.decor_mut().set_prefix("\n    ");
+		members.set_trailing_comma(true);
+	}
+	members.insert_formatted(idx, value);
+
+	Ok(())
+}
+
+/// Adds `deps` to the `[dependencies]` table, keeping ones that are already there
+pub fn add_dependencies<'a>(
+	manifest: &mut DocumentMut,
+	deps: impl IntoIterator<Item = (&'a str, Item)>,
+) -> anyhow::Result<()> {
+	let dependencies = manifest
+		.entry("dependencies")
+		.or_insert(toml_edit::table())
+		.as_table_mut()
+		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
+
+	for (name, dependency) in deps {
+		if !dependencies.contains_key(name) {
+			dependencies.insert(name, dependency);
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use toml_edit::DocumentMut;
+
+	const WORKSPACE: &str = "# Advent of Code 2023
+[workspace]
+members = [
+    'aqa_aoc',
+    'day_1',
+    'day_2', # the one with the cubes
+    'day_9',
+]
+resolver = '2'
+";
+
+	#[test]
+	fn insert_member_in_numeric_order() {
+		let mut manifest = WORKSPACE.parse::<DocumentMut>().unwrap();
+
+		super::insert_workspace_member(&mut manifest, "day_10").unwrap();
+		super::insert_workspace_member(&mut manifest, "day_3").unwrap();
+		super::insert_workspace_member(&mut manifest, "day_3").unwrap();
+
+		assert_eq!(
+			manifest.to_string(),
+			"# Advent of Code 2023
+[workspace]
+members = [
+    'aqa_aoc',
+    'day_1',
+    'day_2', # the one with the cubes
+    'day_3',
+    'day_9',
+    'day_10',
+]
+resolver = '2'
+"
+		);
+		assert_eq!(
+			super::workspace_members(&manifest).unwrap(),
+			["aqa_aoc", "day_1", "day_2", "day_3", "day_9", "day_10"]
+		);
+	}
+
+	#[test]
+	fn insert_member_keeps_double_quotes() {
+		let mut manifest = "[workspace]\nmembers = [\"day_2\", \"day_11\"]\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+
+		super::insert_workspace_member(&mut manifest, "day_10").unwrap();
+
+		assert_eq!(
+			manifest.to_string(),
+			"[workspace]\nmembers = [\"day_2\", \"day_10\", \"day_11\"]\n"
+		);
+	}
+
+	#[test]
+	fn insert_member_into_empty_array() {
+		let mut manifest = "[workspace]\nmembers = [\n]\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+
+		super::insert_workspace_member(&mut manifest, "day_1").unwrap();
+
+		assert_eq!(
+			manifest.to_string(),
+			"[workspace]\nmembers = [\n    \"day_1\",\n]\n"
+		);
+	}
+
+	#[test]
+	fn insert_member_in_year_order() {
+		let mut manifest = "[workspace]\nmembers = ['aqa_aoc', '2022/day_3', '2023/day_1']\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+
+		super::insert_workspace_member(&mut manifest, "2022/day_12").unwrap();
+		super::insert_workspace_member(&mut manifest, "2023/day_2").unwrap();
+
+		assert_eq!(
+			super::workspace_members(&manifest).unwrap(),
+			[
+				"aqa_aoc",
+				"2022/day_3",
+				"2022/day_12",
+				"2023/day_1",
+				"2023/day_2"
+			]
+		);
+	}
+
+	#[test]
+	fn missing_members() {
+		let mut manifest = "[package]\nname = \"day_1\"\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+		assert!(super::insert_workspace_member(&mut manifest, "day_2").is_err());
+	}
+
+	#[test]
+	fn add_dependencies() {
+		let mut manifest = "[package]
+name = \"day_13\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+itertools = \"0.12\"
+"
+		.parse::<DocumentMut>()
+		.unwrap();
+
+		super::add_dependencies(
+			&mut manifest,
+			[
+				("anyhow", toml_edit::value("1.0")),
+				("itertools", toml_edit::value("0.10")),
+			],
+		)
+		.unwrap();
+
+		assert_eq!(
+			manifest.to_string(),
+			"[package]
+name = \"day_13\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+itertools = \"0.12\"
+anyhow = \"1.0\"
+"
+		);
+	}
+}
@@ -0,0 +1,329 @@
+use crate::client::AocClient;
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PuzzleParserError {
+	#[error("no <article class=\"day-desc\"> found in the puzzle page")]
+	NoArticles,
+}
+
+pub fn get_puzzle_page(client: &AocClient, year: u32, day: u32) -> anyhow::Result<String> {
+	client.get(&format!("/{year}/day/{day}"), "fetch puzzle")
+}
+
+/// Converts every `<article class="day-desc">` of the puzzle page into Markdown. Once part 1 is
+/// solved the page contains two of them, one per part.
+///
+/// `page_url` is used to turn relative links into absolute ones.
+pub fn puzzle_to_markdown(html: &str, page_url: &str) -> anyhow::Result<String> {
+	let document = Html::parse_document(html);
+	let selector = Selector::parse("article.day-desc").unwrap();
+
+	let articles: Vec<String> = document
+		.select(&selector)
+		.map(|article| {
+			let mut converter = Converter {
+				page_url,
+				out: String::new(),
+			};
+			converter.block_children(article);
+			converter.out.trim().to_string()
+		})
+		.collect();
+
+	if articles.is_empty() {
+		return Err(PuzzleParserError::NoArticles.into());
+	}
+
+	let mut markdown = articles.join("\n\n");
+	markdown.push('\n');
+	Ok(markdown)
+}
+
+struct Converter<'a> {
+	page_url: &'a str,
+	out: String,
+}
+
+impl Converter<'_> {
+	fn block_children(&mut self, element: ElementRef) {
+		for child in element.children() {
+			match child.value() {
+				Node::Element(_) => self.block(ElementRef::wrap(child).unwrap()),
+				Node::Text(text) if !text.trim().is_empty() => {
+					let text = inline_text(text);
+					self.paragraph(text.trim());
+				}
+				_ => (),
+			}
+		}
+	}
+
+	fn block(&mut self, element: ElementRef) {
+		match element.value().name() {
+			"h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+				let level = element.value().name()[1..].parse::<usize>().unwrap();
+				let text = self.inline_children(element);
+				self.paragraph(&format!("{} {}", "#".repeat(level), text.trim()));
+			}
+			"p" => {
+				let text = self.inline_children(element);
+				self.paragraph(text.trim());
+			}
+			"pre" => {
+				let code: String = element.text().collect();
+				let code = code.strip_suffix('\n').unwrap_or(&code);
+				self.paragraph(&format!("```\n{}\n```", code));
+			}
+			"ul" | "ol" => {
+				let ordered = element.value().name() == "ol";
+				let items: Vec<String> = element
+					.children()
+					.filter_map(ElementRef::wrap)
+					.filter(|child| child.value().name() == "li")
+					.enumerate()
+					.map(|(idx, item)| {
+						let marker = if ordered {
+							format!("{}. ", idx + 1)
+						} else {
+							String::from("- ")
+						};
+						let indent = " ".repeat(marker.len());
+						let text = self.list_item(item);
+						let text = text.trim().replace('\n', &format!("\n{}", indent));
+						format!("{}{}", marker, text)
+					})
+					.collect();
+				self.paragraph(&items.join("\n"));
+			}
+			_ => {
+				let text = self.inline(element);
+				if !text.trim().is_empty() {
+					self.paragraph(text.trim());
+				}
+			}
+		}
+	}
+
+	/// List items may contain nested lists or paragraphs, which get converted as blocks
+	fn list_item(&self, item: ElementRef) -> String {
+		let has_blocks = item
+			.children()
+			.filter_map(ElementRef::wrap)
+			.any(|child| matches!(child.value().name(), "p" | "ul" | "ol" | "pre"));
+		if !has_blocks {
+			return self.inline_children(item);
+		}
+
+		let mut nested = Converter {
+			page_url: self.page_url,
+			out: String::new(),
+		};
+		nested.block_children(item);
+		nested.out
+	}
+
+	fn paragraph(&mut self, text: &str) {
+		if !self.out.is_empty() {
+			self.out.push_str("\n\n");
+		}
+		self.out.push_str(text);
+	}
+
+	fn inline_children(&self, element: ElementRef) -> String {
+		let mut out = String::new();
+		for child in element.children() {
+			match child.value() {
+				Node::Text(text) => out.push_str(&inline_text(text)),
+				Node::Element(_) => out.push_str(&self.inline(ElementRef::wrap(child).unwrap())),
+				_ => (),
+			}
+		}
+		out
+	}
+
+	fn inline(&self, element: ElementRef) -> String {
+		match element.value().name() {
+			"em" | "i" => wrap_inline(&self.inline_children(element), "*"),
+			"strong" | "b" => wrap_inline(&self.inline_children(element), "**"),
+			"code" => {
+				let code: String = element.text().collect();
+				let code = format!("`{}`", code);
+				// Answers are usually highlighted as <code><em>42</em></code>
+				let emphasized = element
+					.children()
+					.filter_map(ElementRef::wrap)
+					.any(|child| child.value().name() == "em");
+				if emphasized {
+					wrap_inline(&code, "*")
+				} else {
+					code
+				}
+			}
+			"a" => {
+				let text = self.inline_children(element);
+				match element.value().attr("href") {
+					Some(href) => format!("[{}]({})", text.trim(), self.resolve_link(href)),
+					None => text,
+				}
+			}
+			"br" => String::from("  \n"),
+			_ => self.inline_children(element),
+		}
+	}
+
+	fn resolve_link(&self, href: &str) -> String {
+		if href.contains("://") {
+			return href.to_string();
+		}
+
+		let (scheme, rest) = self
+			.page_url
+			.split_once("://")
+			.unwrap_or(("https", self.page_url));
+		let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+		if let Some(absolute) = href.strip_prefix('/') {
+			format!("{scheme}://{host}/{absolute}")
+		} else if href.starts_with('#') {
+			format!("{}{}", self.page_url, href)
+		} else {
+			let dir = path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+			if dir.is_empty() {
+				format!("{scheme}://{host}/{href}")
+			} else {
+				format!("{scheme}://{host}/{dir}/{href}")
+			}
+		}
+	}
+}
+
+fn inline_text(text: &str) -> String {
+	escape(&collapse_whitespace(text))
+}
+
+/// Puts emphasis markers around `text`, keeping surrounding whitespace outside of them
+fn wrap_inline(text: &str, marker: &str) -> String {
+	let trimmed = text.trim();
+	if trimmed.is_empty() {
+		return text.to_string();
+	}
+	let leading = &text[..text.len() - text.trim_start().len()];
+	let trailing = &text[text.trim_end().len()..];
+	format!("{leading}{marker}{trimmed}{marker}{trailing}")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut last_was_space = false;
+	for c in text.chars() {
+		if c.is_whitespace() {
+			if !last_was_space {
+				out.push(' ');
+			}
+			last_was_space = true;
+		} else {
+			out.push(c);
+			last_was_space = false;
+		}
+	}
+	out
+}
+
+fn escape(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	for c in text.chars() {
+		if matches!(c, '*' | '_' | '`' | '\\') {
+			out.push('\\');
+		}
+		out.push(c);
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	const PAGE_URL: &str = "https://adventofcode.com/2023/day/1";
+
+	#[test]
+	fn convert_fixture_page() {
+		let html = std::fs::read_to_string(concat!(
+			env!("CARGO_MANIFEST_DIR"),
+			"/fixtures/2023/day/1/index.html"
+		))
+		.unwrap();
+
+		let markdown = super::puzzle_to_markdown(&html, PAGE_URL).unwrap();
+		assert_eq!(
+			markdown,
+			"## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a map; on it, they've used stars to mark the top fifty locations that are likely to be having problems.
+
+The newly-improved calibration document consists of lines of text; each line originally contained a specific *calibration value* that the Elves now need to recover. On each line, the calibration value can be found by combining the *first digit* and the *last digit* (in that order) to form a single *two-digit number*.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+```
+
+In this example, the calibration values of these four lines are `12`, `38`, `15`, and `77`. Adding these together produces *`142`*.
+
+Consider your entire calibration document. *What is the sum of all of the calibration values?*
+"
+		);
+	}
+
+	#[test]
+	fn convert_both_parts() {
+		let html = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Gear Ratios ---</h2><p>Part one.</p></article>
+<p>Your puzzle answer was <code>4361</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two.</p></article>
+</main>"#;
+
+		let markdown = super::puzzle_to_markdown(html, PAGE_URL).unwrap();
+		assert_eq!(
+			markdown,
+			"## --- Day 3: Gear Ratios ---\n\nPart one.\n\n## --- Part Two ---\n\nPart two.\n"
+		);
+	}
+
+	#[test]
+	fn convert_lists_and_links() {
+		let html = r#"<article class="day-desc"><p>The rules:</p>
+<ul>
+<li>Every <em>gear</em> is a <code>*</code> symbol.</li>
+<li>See the <a href="/2023/about">about page</a> or <a href="1/input" target="_blank">input</a>.</li>
+</ul>
+<ol><li>First</li><li>Second, <a href="https://example.com/">elsewhere</a></li></ol>
+<p>Multiply 2_000 * 3 <span title="Easter egg">times</span>.</p>
+</article>"#;
+
+		let markdown = super::puzzle_to_markdown(html, PAGE_URL).unwrap();
+		assert_eq!(
+			markdown,
+			"The rules:
+
+- Every *gear* is a `*` symbol.
+- See the [about page](https://adventofcode.com/2023/about) or [input](https://adventofcode.com/2023/day/1/input).
+
+1. First
+2. Second, [elsewhere](https://example.com/)
+
+Multiply 2\\_000 \\* 3 times.
+"
+		);
+	}
+
+	#[test]
+	fn missing_articles() {
+		assert!(super::puzzle_to_markdown("<html><body>Log in</body></html>", PAGE_URL).is_err());
+	}
+}
@@ -0,0 +1,285 @@
+//! Builds and runs the day crates of the workspace, collecting the `Part N: answer` lines they
+//! print and the `Part N solved in T` timings of [`aoc_lib::run`]. Every day binary runs from the
+//! workspace root, like `cargo run -p day_N` would.
+
+use crate::day_crate::{self, DayCrate, Layout};
+use crate::manifest;
+use crate::table::{Align, Table};
+use aoc_lib::format_duration;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub struct DayRun {
+	pub day_crate: DayCrate,
+	/// Parts the binary answered, even if it failed or timed out afterwards
+	pub parts: Vec<PartRun>,
+	/// Why the binary didn't finish
+	pub error: Option<String>,
+	/// Wall-clock time of the whole day binary
+	pub time: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartRun {
+	pub part: u32,
+	pub answer: String,
+	/// Time the day binary took for the part alone, as it printed it
+	pub time: Option<String>,
+}
+
+/// Returns day crates of the workspace, limited to `year` and `day` when they are given. `layout`
+/// tells which year flat crates belong to.
+pub fn select_day_crates(
+	year: Option<u32>,
+	layout: Layout,
+	day: Option<u32>,
+) -> anyhow::Result<Vec<DayCrate>> {
+	let cargo_workspace = manifest::read(Path::new("Cargo.toml"))?;
+	let day_crates: Vec<DayCrate> = day_crate::workspace_day_crates(&cargo_workspace)?
+		.into_iter()
+		.filter(|d| year.is_none_or(|year| d.is_in_year(year, layout)))
+		.filter(|d| day.is_none_or(|day| day == d.day))
+		.collect();
+	if day_crates.is_empty() {
+		anyhow::bail!("no day crates found in the workspace");
+	}
+	Ok(day_crates)
+}
+
+/// Builds and runs `part` (or both parts) of `day_crates`, stopping days that take longer than
+/// `timeout`
+pub fn run_day_crates(
+	day_crates: Vec<DayCrate>,
+	part: Option<u32>,
+	timeout: Duration,
+) -> anyhow::Result<Vec<DayRun>> {
+	build(&day_crates)?;
+
+	Ok(day_crates
+		.into_iter()
+		.map(|day_crate| {
+			println!("Running {}...", day_crate);
+			run_day(day_crate, part, timeout)
+		})
+		.collect())
+}
+
+/// Builds, runs and prints a table of the answers of the selected days and `part` (or both)
+pub fn run(
+	year: Option<u32>,
+	layout: Layout,
+	day: Option<u32>,
+	part: Option<u32>,
+	timeout: Duration,
+) -> anyhow::Result<()> {
+	let mut table = Table::new([
+		("Day", Align::Right),
+		("Part", Align::Right),
+		("Answer", Align::Left),
+		("Time", Align::Right),
+	]);
+	let mut failed = 0;
+	for run in run_day_crates(select_day_crates(year, layout, day)?, part, timeout)? {
+		for part_run in &run.parts {
+			table.push_row(vec![
+				run.day_crate.label(),
+				part_run.part.to_string(),
+				part_run.answer.clone(),
+				part_run.time.clone().unwrap_or_default(),
+			]);
+		}
+		if let Some(err) = &run.error {
+			failed += 1;
+			table.push_row(vec![
+				run.day_crate.label(),
+				String::from("-"),
+				format!("error: {}", err),
+				format_duration(run.time),
+			]);
+		}
+	}
+
+	println!();
+	print!("{}", table);
+
+	if failed > 0 {
+		anyhow::bail!("{} day(s) failed", failed);
+	}
+	Ok(())
+}
+
+fn build(day_crates: &[DayCrate]) -> anyhow::Result<()> {
+	let mut cargo = Command::new("cargo");
+	cargo.args(["build", "--release"]);
+	for day_crate in day_crates {
+		cargo.args(["-p", &day_crate.package_name()]);
+	}
+
+	let status = cargo.status()?;
+	if !status.success() {
+		return Err(anyhow::anyhow!(
+			"cargo build failed with code {}",
+			status.code().unwrap_or_default()
+		));
+	}
+	Ok(())
+}
+
+/// Runs the binary of `day_crate`, asking it for `part` only when given, and kills it after
+/// `timeout`
+pub fn run_day(day_crate: DayCrate, part: Option<u32>, timeout: Duration) -> DayRun {
+	let failed = |error: String| DayRun {
+		day_crate,
+		parts: Vec::new(),
+		error: Some(error),
+		time: Duration::ZERO,
+	};
+
+	let input_path = day_crate.dir().join("input.txt");
+	if !input_path.exists() {
+		return failed(format!("{} not found", input_path.display()));
+	}
+
+	let start = Instant::now();
+	let mut command = Command::new(executable_path(&day_crate.package_name()));
+	if let Some(part) = part {
+		command.arg(format!("--part={}", part));
+	}
+	let mut child = match command
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+	{
+		Ok(child) => child,
+		Err(err) => return failed(err.to_string()),
+	};
+	// Read while the binary runs, so it doesn't block on a full pipe
+	let stdout = read_in_background(child.stdout.take());
+	let stderr = read_in_background(child.stderr.take());
+
+	let status = loop {
+		match child.try_wait() {
+			Ok(Some(status)) => break Ok(status),
+			Ok(None) if start.elapsed() >= timeout => {
+				let _ = child.kill();
+				let _ = child.wait();
+				break Err(format!("timed out after {}", format_duration(timeout)));
+			}
+			Ok(None) => thread::sleep(Duration::from_millis(10)),
+			Err(err) => break Err(err.to_string()),
+		}
+	};
+	let time = start.elapsed();
+	let stdout = stdout.join().unwrap_or_default();
+	let stderr = stderr.join().unwrap_or_default();
+
+	let error = match status {
+		Ok(status) if status.success() => None,
+		Ok(status) => Some(
+			stderr
+				.lines()
+				.rev()
+				.find(|line| !line.trim().is_empty())
+				.map(str::to_string)
+				.unwrap_or_else(|| format!("exited with {}", status)),
+		),
+		Err(err) => Some(err),
+	};
+
+	DayRun {
+		day_crate,
+		parts: parse_parts(&stdout, &stderr),
+		error,
+		time,
+	}
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+	thread::spawn(move || {
+		let mut out = Vec::new();
+		if let Some(mut pipe) = pipe {
+			let _ = pipe.read_to_end(&mut out);
+		}
+		String::from_utf8_lossy(&out).into_owned()
+	})
+}
+
+fn executable_path(crate_name: &str) -> PathBuf {
+	let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+		.map(PathBuf::from)
+		.unwrap_or_else(|| PathBuf::from("target"));
+	target_dir
+		.join("release")
+		.join(format!("{}{}", crate_name, std::env::consts::EXE_SUFFIX))
+}
+
+/// Picks `Part N: answer` lines out of the output of a day binary
+pub fn parse_answers(stdout: &str) -> Vec<(u32, String)> {
+	stdout
+		.lines()
+		.filter_map(|line| {
+			let (part, answer) = line.strip_prefix("Part ")?.split_once(':')?;
+			Some((part.trim().parse::<u32>().ok()?, answer.trim().to_string()))
+		})
+		.collect()
+}
+
+/// The answers of a day binary with the `Part N solved in T` times it printed to stderr
+fn parse_parts(stdout: &str, stderr: &str) -> Vec<PartRun> {
+	let times: Vec<(u32, &str)> = stderr
+		.lines()
+		.filter_map(|line| {
+			let (part, time) = line.strip_prefix("Part ")?.split_once(" solved in ")?;
+			Some((part.trim().parse::<u32>().ok()?, time.trim()))
+		})
+		.collect();
+	parse_answers(stdout)
+		.into_iter()
+		.map(|(part, answer)| PartRun {
+			part,
+			answer,
+			time: times
+				.iter()
+				.find(|(p, _)| *p == part)
+				.map(|(_, time)| time.to_string()),
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PartRun;
+
+	#[test]
+	fn parse_answers() {
+		let stdout = "0 = 1\n1 = 4\nPart 1: 54239\nPart 2: 55343\n";
+		assert_eq!(
+			super::parse_answers(stdout),
+			[(1, String::from("54239")), (2, String::from("55343"))]
+		);
+	}
+
+	#[test]
+	fn parse_parts() {
+		let stderr = "Parsed in 15µs\nPart 1 solved in 2.5ms\nPart 2 solved in 1.20s\n";
+		assert_eq!(
+			super::parse_parts("Part 1: 54239\nPart 2: 55343\n", stderr),
+			[
+				PartRun {
+					part: 1,
+					answer: String::from("54239"),
+					time: Some(String::from("2.5ms")),
+				},
+				PartRun {
+					part: 2,
+					answer: String::from("55343"),
+					time: Some(String::from("1.20s")),
+				},
+			]
+		);
+		assert_eq!(super::parse_parts("Part 2: 7\n", "")[0].time, None);
+	}
+}
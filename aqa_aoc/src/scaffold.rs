@@ -0,0 +1,423 @@
+//! Day crate templates. A template is a directory whose files are copied into the new crate,
+//! with `{{day}}`, `{{year}}`, `{{crate_name}}` and `{{workspace_root}}` replaced in both file
+//! contents and paths.
+//! Dependencies from `scaffold.dependencies` are then added to the manifest of the new crate.
+
+use crate::changes::Changes;
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use toml_edit::{Array, InlineTable, Item, Value};
+
+/// Built-in template, used when `scaffold.template` is not set in aoc_cfg.toml
+static DEFAULT_TEMPLATE: &[(&str, &str)] = &[
+	(
+		"Cargo.toml",
+		include_str!("../templates/default/Cargo.toml"),
+	),
+	(
+		"src/main.rs",
+		include_str!("../templates/default/src/main.rs"),
+	),
+];
+
+/// Dependencies of every new crate, on top of `scaffold.dependencies` from aoc_cfg.toml
+static DEFAULT_DEPENDENCIES: &[(&str, &str)] = &[
+	("anyhow", "1.0"),
+	("itertools", "0.10"),
+	("nom", "7.0.0"),
+	("regex", "1.4"),
+];
+
+/// Workspace crates every new crate depends on, with paths relative to the workspace root
+static DEFAULT_PATH_DEPENDENCIES: &[(&str, &str)] = &[("aoc_lib", "aoc_lib")];
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+	#[error("unknown template variable \"{0}\"")]
+	UnknownVariable(String),
+	#[error("unclosed \"{{{{\" in template")]
+	UnclosedVariable,
+	#[error("{0} already exists")]
+	CrateExists(PathBuf),
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ScaffoldCfg {
+	/// Template directory, relative to the workspace root
+	pub template: Option<PathBuf>,
+	/// Dependencies added to every new crate next to the default ones, in the same format as in
+	/// Cargo.toml. Paths are relative to the workspace root.
+	pub dependencies: Option<BTreeMap<String, DependencyCfg>>,
+}
+
+impl ScaffoldCfg {
+	/// The default dependencies merged with the configured ones, which win over defaults of the
+	/// same name
+	pub fn dependencies(&self) -> BTreeMap<String, DependencyCfg> {
+		let mut dependencies: BTreeMap<String, DependencyCfg> = DEFAULT_DEPENDENCIES
+			.iter()
+			.map(|(name, version)| {
+				(
+					name.to_string(),
+					DependencyCfg::Version(version.to_string()),
+				)
+			})
+			.chain(DEFAULT_PATH_DEPENDENCIES.iter().map(|(name, path)| {
+				(
+					name.to_string(),
+					DependencyCfg::Detailed(DetailedDependencyCfg {
+						path: Some(PathBuf::from(path)),
+						..Default::default()
+					}),
+				)
+			}))
+			.collect();
+		if let Some(configured) = &self.dependencies {
+			dependencies.extend(configured.clone());
+		}
+		dependencies
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum DependencyCfg {
+	Version(String),
+	Detailed(DetailedDependencyCfg),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetailedDependencyCfg {
+	pub version: Option<String>,
+	pub path: Option<PathBuf>,
+	pub git: Option<String>,
+	pub branch: Option<String>,
+	pub tag: Option<String>,
+	pub rev: Option<String>,
+	#[serde(default)]
+	pub features: Vec<String>,
+	pub default_features: Option<bool>,
+	pub package: Option<String>,
+}
+
+impl DependencyCfg {
+	/// Converts the dependency into its Cargo.toml form for the crate in `crate_dir` (relative to
+	/// the workspace root), rebasing the path onto it
+	pub fn to_toml(&self, crate_dir: &Path) -> Item {
+		let dependency = match self {
+			DependencyCfg::Version(version) => return toml_edit::value(version),
+			DependencyCfg::Detailed(dependency) => dependency,
+		};
+
+		let mut table = InlineTable::new();
+		let strings = [
+			("package", &dependency.package),
+			("version", &dependency.version),
+			(
+				"path",
+				&dependency
+					.path
+					.as_ref()
+					.map(|path| relative_to_crate(path, crate_dir)),
+			),
+			("git", &dependency.git),
+			("branch", &dependency.branch),
+			("tag", &dependency.tag),
+			("rev", &dependency.rev),
+		];
+		for (key, value) in strings {
+			if let Some(value) = value {
+				table.insert(key, Value::from(value.as_str()));
+			}
+		}
+		if !dependency.features.is_empty() {
+			let features: Array = dependency.features.iter().map(String::as_str).collect();
+			table.insert("features", Value::Array(features));
+		}
+		if let Some(default_features) = dependency.default_features {
+			table.insert("default-features", Value::from(default_features));
+		}
+
+		Item::Value(Value::InlineTable(table))
+	}
+}
+
+/// Turns a path relative to the workspace root into one relative to `crate_dir`, with forward
+/// slashes so the manifest stays the same on every platform
+fn relative_to_crate(path: &Path, crate_dir: &Path) -> String {
+	if path.is_absolute() {
+		return path.display().to_string();
+	}
+
+	let depth = crate_dir
+		.components()
+		.filter(|c| matches!(c, Component::Normal(_)))
+		.count();
+	let mut parts = vec![".."; depth];
+	parts.extend(path.components().filter_map(|c| match c {
+		Component::CurDir => None,
+		c => c.as_os_str().to_str(),
+	}));
+	parts.join("/")
+}
+
+/// Path from `crate_dir` (relative to the workspace root) back to the workspace root
+pub fn workspace_root(crate_dir: &Path) -> String {
+	relative_to_crate(Path::new(""), crate_dir)
+}
+
+pub struct TemplateVars<'a> {
+	pub day: u32,
+	pub year: u32,
+	pub crate_name: &'a str,
+	/// Path from the new crate to the workspace root, like `..` or `../..`
+	pub workspace_root: &'a str,
+}
+
+impl TemplateVars<'_> {
+	fn get(&self, name: &str) -> Option<String> {
+		match name {
+			"day" => Some(self.day.to_string()),
+			"year" => Some(self.year.to_string()),
+			"crate_name" => Some(self.crate_name.to_string()),
+			"workspace_root" => Some(self.workspace_root.to_string()),
+			_ => None,
+		}
+	}
+}
+
+pub fn render(template: &str, vars: &TemplateVars) -> Result<String, TemplateError> {
+	let mut out = String::with_capacity(template.len());
+	let mut rest = template;
+	while let Some(start) = rest.find("{{") {
+		out.push_str(&rest[..start]);
+		let end = rest[start..]
+			.find("}}")
+			.ok_or(TemplateError::UnclosedVariable)?;
+		let name = rest[(start + 2)..(start + end)].trim();
+		let value = vars
+			.get(name)
+			.ok_or_else(|| TemplateError::UnknownVariable(name.to_string()))?;
+		out.push_str(&value);
+		rest = &rest[(start + end + 2)..];
+	}
+	out.push_str(rest);
+	Ok(out)
+}
+
+/// Renders `template` (or the built-in one) into `crate_dir`, which must not exist yet
+pub fn create_crate(
+	changes: &Changes,
+	crate_dir: &Path,
+	template: Option<&Path>,
+	vars: &TemplateVars,
+) -> anyhow::Result<()> {
+	if changes.exists(crate_dir) {
+		return Err(TemplateError::CrateExists(crate_dir.to_path_buf()).into());
+	}
+
+	let files = match template {
+		Some(template) => read_template_dir(template)
+			.with_context(|| format!("failed to read template {}", template.display()))?,
+		None => DEFAULT_TEMPLATE
+			.iter()
+			.map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+			.collect(),
+	};
+
+	for (path, contents) in files {
+		let path = crate_dir.join(render(&path.to_string_lossy(), vars)?);
+		let contents = render(&contents, vars)
+			.with_context(|| format!("failed to render {}", path.display()))?;
+		if let Some(parent) = path.parent() {
+			changes.create_dir_all(parent)?;
+		}
+		changes.write(&path, &contents)?;
+	}
+
+	Ok(())
+}
+
+/// Reads all files of the template directory, with paths relative to it
+fn read_template_dir(template: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+	let mut files = Vec::new();
+	let mut dirs = vec![template.to_path_buf()];
+	while let Some(dir) = dirs.pop() {
+		for entry in fs::read_dir(&dir)? {
+			let path = entry?.path();
+			if path.is_dir() {
+				dirs.push(path);
+			} else {
+				let contents = fs::read_to_string(&path)
+					.with_context(|| format!("failed to read {}", path.display()))?;
+				files.push((path.strip_prefix(template)?.to_path_buf(), contents));
+			}
+		}
+	}
+	files.sort();
+	Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::TemplateVars;
+	use crate::changes::Changes;
+	use std::fs;
+
+	const VARS: TemplateVars = TemplateVars {
+		day: 13,
+		year: 2023,
+		crate_name: "day_13",
+		workspace_root: "..",
+	};
+
+	#[test]
+	fn render() {
+		assert_eq!(
+			super::render("{{crate_name}}/input.txt {{ year }}-{{day}}", &VARS).unwrap(),
+			"day_13/input.txt 2023-13"
+		);
+		assert!(super::render("{{month}}", &VARS).is_err());
+		assert!(super::render("{{day", &VARS).is_err());
+	}
+
+	#[test]
+	fn dependencies_cfg() {
+		let cfg: super::ScaffoldCfg = toml::from_str(
+			r#"
+[dependencies]
+anyhow = "1.0"
+rayon = { version = "1.8", features = ["web_spin_lock"], default-features = false }
+aoc_lib = { path = "./aoc_lib" }
+"#,
+		)
+		.unwrap();
+
+		let dependencies = cfg.dependencies();
+		let crate_dir = std::path::Path::new("day_13");
+		let rendered: Vec<(String, String)> = dependencies
+			.iter()
+			.map(|(name, dep)| (name.clone(), dep.to_toml(crate_dir).to_string()))
+			.collect();
+		assert_eq!(
+			rendered,
+			[
+				("anyhow", "\"1.0\""),
+				("aoc_lib", "{ path = \"../aoc_lib\" }"),
+				("itertools", "\"0.10\""),
+				("nom", "\"7.0.0\""),
+				(
+					"rayon",
+					"{ version = \"1.8\", features = [\"web_spin_lock\"], default-features = false }"
+				),
+				("regex", "\"1.4\""),
+			]
+			.map(|(name, dep)| (name.to_string(), dep.to_string()))
+		);
+
+		assert_eq!(
+			super::relative_to_crate(std::path::Path::new("aoc_lib"), "2023/day_1".as_ref()),
+			"../../aoc_lib"
+		);
+		assert_eq!(super::workspace_root("2023/day_1".as_ref()), "../..");
+	}
+
+	#[test]
+	fn default_dependencies() {
+		let dependencies = super::ScaffoldCfg::default().dependencies();
+		assert_eq!(
+			dependencies.keys().collect::<Vec<_>>(),
+			["anyhow", "aoc_lib", "itertools", "nom", "regex"]
+		);
+		assert_eq!(
+			dependencies["aoc_lib"]
+				.to_toml(std::path::Path::new("day_13"))
+				.to_string(),
+			"{ path = \"../aoc_lib\" }"
+		);
+	}
+
+	#[test]
+	fn configured_dependencies_override_defaults() {
+		let cfg: super::ScaffoldCfg = toml::from_str(
+			r#"
+[dependencies]
+itertools = "0.12"
+regex = { version = "1.10", default-features = false }
+"#,
+		)
+		.unwrap();
+
+		let dependencies = cfg.dependencies();
+		assert_eq!(
+			dependencies.keys().collect::<Vec<_>>(),
+			["anyhow", "aoc_lib", "itertools", "nom", "regex"]
+		);
+		let crate_dir = std::path::Path::new("day_13");
+		assert_eq!(
+			dependencies["itertools"].to_toml(crate_dir).to_string(),
+			"\"0.12\""
+		);
+		assert_eq!(
+			dependencies["regex"].to_toml(crate_dir).to_string(),
+			"{ version = \"1.10\", default-features = false }"
+		);
+		assert_eq!(
+			dependencies["anyhow"].to_toml(crate_dir).to_string(),
+			"\"1.0\""
+		);
+	}
+
+	#[test]
+	fn create_from_default_template() {
+		let dir = tempfile::tempdir().unwrap();
+		let crate_dir = dir.path().join("day_13");
+
+		super::create_crate(&Changes::default(), &crate_dir, None, &VARS).unwrap();
+
+		let cargo_toml = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap();
+		assert!(cargo_toml.contains("name = \"day_13\""));
+		assert!(cargo_toml.contains("aoc_lib = { path = \"../aoc_lib\" }"));
+		let main = fs::read_to_string(crate_dir.join("src/main.rs")).unwrap();
+		assert!(main.contains("aoc_lib::main!(Day);"));
+		assert!(main.contains("todo!(\"2023 day 13 part 1\")"));
+		assert!(main.contains("todo!(\"2023 day 13 part 2\")"));
+		assert!(main.contains("mod tests"));
+		// Until the example is filled in
+		assert_eq!(main.matches("#[ignore").count(), 2);
+
+		assert!(super::create_crate(&Changes::default(), &crate_dir, None, &VARS).is_err());
+	}
+
+	#[test]
+	fn create_from_template_dir() {
+		let dir = tempfile::tempdir().unwrap();
+		let template = dir.path().join("template");
+		fs::create_dir_all(template.join("src")).unwrap();
+		fs::write(
+			template.join("Cargo.toml"),
+			"[package]\nname = \"{{crate_name}}\"\n",
+		)
+		.unwrap();
+		fs::write(template.join("src/main.rs"), "// Day {{day}} of {{year}}\n").unwrap();
+		fs::write(template.join("{{crate_name}}.md"), "# Notes\n").unwrap();
+
+		let crate_dir = dir.path().join("day_13");
+		super::create_crate(&Changes::default(), &crate_dir, Some(&template), &VARS).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap(),
+			"[package]\nname = \"day_13\"\n"
+		);
+		assert_eq!(
+			fs::read_to_string(crate_dir.join("src/main.rs")).unwrap(),
+			"// Day 13 of 2023\n"
+		);
+		assert!(crate_dir.join("day_13.md").exists());
+	}
+}
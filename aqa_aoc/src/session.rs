@@ -0,0 +1,48 @@
+use crate::client::AocClient;
+use scraper::{Html, Selector};
+
+/// Returns the name of the user the session cookie belongs to, or `None` if the site doesn't
+/// recognise it
+pub fn whoami(client: &AocClient) -> anyhow::Result<Option<String>> {
+	let html = client.get("/settings", "fetch settings")?;
+	Ok(parse_user(&html))
+}
+
+/// Reads the user name from the header of a site page, present only when logged in
+pub fn parse_user(html: &str) -> Option<String> {
+	let document = Html::parse_document(html);
+	let selector = Selector::parse("header div.user").unwrap();
+	let user = document.select(&selector).next()?;
+
+	// Skips the star count following the name
+	let name = user.text().next()?.trim();
+	(!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::client::test_client;
+	use crate::fixture_server::{FixtureServer, FIXTURES_DIR};
+
+	#[test]
+	fn parse_user() {
+		let logged_in = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">(anonymous user #123456) <span class="star-count">42*</span></div></div></header>"#;
+		let logged_out = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2023/auth/login">[Log In]</a></li></ul></nav></div></header>"#;
+
+		assert_eq!(
+			super::parse_user(logged_in).as_deref(),
+			Some("(anonymous user #123456)")
+		);
+		assert_eq!(super::parse_user(logged_out), None);
+	}
+
+	#[test]
+	fn whoami_on_fixture_server() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+
+		let user = super::whoami(&test_client(&server.addr(), cache_dir.path())).unwrap();
+		assert_eq!(user.as_deref(), Some("aQaTL"));
+		assert_eq!(server.requests()[0].path, "/settings");
+	}
+}
@@ -0,0 +1,235 @@
+//! Overview of the day crates of the workspace
+
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::day_crate::{self, DayCrate, Layout};
+use crate::manifest;
+use crate::table::{Align, Table};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Stars of an event from the site, with the layout to place day crates that don't exist yet
+pub struct Calendar {
+	pub year: u32,
+	pub layout: Layout,
+	pub stars: BTreeMap<u32, u32>,
+}
+
+pub fn print_status(
+	year: Option<u32>,
+	layout: Layout,
+	day: Option<u32>,
+	calendar: Option<&Calendar>,
+) -> anyhow::Result<()> {
+	let cargo_workspace = manifest::read(Path::new("Cargo.toml"))?;
+	let answers = Answers::load(Path::new(ANSWERS_FILE))?;
+
+	print!(
+		"{}",
+		status_table(
+			&cargo_workspace,
+			&answers,
+			Path::new("."),
+			year,
+			layout,
+			day,
+			calendar
+		)?
+	);
+	Ok(())
+}
+
+/// One row per day crate and per unlocked day of `calendar`, with what's left to do
+fn status_table(
+	cargo_workspace: &toml_edit::DocumentMut,
+	answers: &Answers,
+	workspace_dir: &Path,
+	year: Option<u32>,
+	layout: Layout,
+	day: Option<u32>,
+	calendar: Option<&Calendar>,
+) -> anyhow::Result<Table> {
+	let year = calendar.map(|calendar| calendar.year).or(year);
+	let layout = calendar.map_or(layout, |calendar| calendar.layout);
+	let members: BTreeSet<DayCrate> = day_crate::workspace_day_crates(cargo_workspace)?
+		.into_iter()
+		.filter(|d| year.is_none_or(|year| d.is_in_year(year, layout)))
+		.filter(|d| day.is_none_or(|day| day == d.day))
+		.collect();
+	let mut day_crates = members.clone();
+	if let Some(calendar) = calendar {
+		day_crates.extend(
+			calendar
+				.stars
+				.keys()
+				.filter(|d| day.is_none_or(|day| day == **d))
+				.filter(|d| !members.iter().any(|member| member.day == **d))
+				.map(|d| DayCrate::new(calendar.layout, calendar.year, *d)),
+		);
+	}
+
+	let mut table = Table::new([
+		("Day", Align::Right),
+		("Stars", Align::Left),
+		("Crate", Align::Left),
+		("Input", Align::Left),
+		("Puzzle", Align::Left),
+		("Examples", Align::Right),
+		("Tests", Align::Right),
+		("Answers", Align::Left),
+		("To do", Align::Left),
+	]);
+
+	for day_crate in day_crates {
+		let is_member = members.contains(&day_crate);
+		let crate_dir = workspace_dir.join(day_crate.dir());
+		let yes_no = |exists: bool| {
+			if exists {
+				String::from("yes")
+			} else {
+				String::from("-")
+			}
+		};
+		let examples = std::fs::read_dir(crate_dir.join("examples"))
+			.map(|dir| dir.count())
+			.unwrap_or_default();
+		let recorded: Vec<u32> = (1..=2)
+			.filter(|part| answers.get(&day_crate, *part).is_some())
+			.collect();
+		let stars = calendar.and_then(|calendar| calendar.stars.get(&day_crate.day).copied());
+		let has_input = crate_dir.join("input.txt").exists();
+
+		// The site knows best which parts are solved, answers.toml is the fallback
+		let solved = stars.unwrap_or(recorded.len() as u32);
+		let to_do = if solved == 2 {
+			String::new()
+		} else if !is_member {
+			String::from("new crate")
+		} else if !has_input {
+			String::from("fetch input")
+		} else {
+			format!("part {}", solved + 1)
+		};
+
+		table.push_row(vec![
+			day_crate.label(),
+			match stars {
+				Some(stars) => "*".repeat(stars as usize),
+				None => String::from("?"),
+			},
+			yes_no(is_member),
+			yes_no(has_input),
+			yes_no(crate_dir.join("puzzle.md").exists()),
+			examples.to_string(),
+			count_tests(&crate_dir.join("src")).to_string(),
+			if recorded.is_empty() {
+				String::from("-")
+			} else {
+				recorded
+					.iter()
+					.map(|part| format!("part {}", part))
+					.collect::<Vec<_>>()
+					.join(", ")
+			},
+			to_do,
+		]);
+	}
+
+	Ok(table)
+}
+
+/// Counts `#[test]` functions in the Rust files under `dir`
+fn count_tests(dir: &Path) -> usize {
+	let entries = match std::fs::read_dir(dir) {
+		Ok(entries) => entries,
+		Err(_) => return 0,
+	};
+	entries
+		.filter_map(Result::ok)
+		.map(|entry| entry.path())
+		.map(|path| {
+			if path.is_dir() {
+				count_tests(&path)
+			} else if path.extension().is_some_and(|ext| ext == "rs") {
+				std::fs::read_to_string(&path)
+					.map(|code| code.matches("#[test]").count())
+					.unwrap_or_default()
+			} else {
+				0
+			}
+		})
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Calendar;
+	use crate::answers::Answers;
+	use crate::day_crate::{DayCrate, Layout};
+	use std::collections::BTreeMap;
+	use std::fs;
+
+	#[test]
+	fn status_table() {
+		let dir = tempfile::tempdir().unwrap();
+		fs::create_dir_all(dir.path().join("day_1/examples")).unwrap();
+		fs::create_dir_all(dir.path().join("day_1/src")).unwrap();
+		fs::write(dir.path().join("day_1/input.txt"), "1abc2\n").unwrap();
+		fs::write(dir.path().join("day_1/examples/example_1.txt"), "1abc2\n").unwrap();
+		fs::write(
+			dir.path().join("day_1/src/main.rs"),
+			"#[test]\nfn part_1() {}\n#[test]\nfn part_2() {}\n",
+		)
+		.unwrap();
+		fs::create_dir_all(dir.path().join("day_2")).unwrap();
+
+		let cargo_workspace = "[workspace]\nmembers = ['aqa_aoc', 'day_1', 'day_2']\n"
+			.parse()
+			.unwrap();
+		let mut answers = Answers::default();
+		answers
+			.set(&DayCrate::new(Layout::Flat, 2023, 1), 1, "142")
+			.unwrap();
+
+		let table = super::status_table(
+			&cargo_workspace,
+			&answers,
+			dir.path(),
+			None,
+			Layout::Flat,
+			None,
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			table.to_string(),
+			"Day  Stars  Crate  Input  Puzzle  Examples  Tests  Answers  To do
+  1  ?      yes    yes    -              1      2  part 1   part 2
+  2  ?      yes    -      -              0      0  -        fetch input
+"
+		);
+
+		let calendar = Calendar {
+			year: 2023,
+			layout: Layout::Flat,
+			stars: BTreeMap::from([(1, 2), (2, 0), (3, 0)]),
+		};
+		let table = super::status_table(
+			&cargo_workspace,
+			&answers,
+			dir.path(),
+			None,
+			Layout::Flat,
+			None,
+			Some(&calendar),
+		)
+		.unwrap();
+		assert_eq!(
+			table.to_string(),
+			"Day  Stars  Crate  Input  Puzzle  Examples  Tests  Answers  To do
+  1  **     yes    yes    -              1      2  part 1
+  2         yes    -      -              0      0  -        fetch input
+  3         -      -      -              0      0  -        new crate
+"
+		);
+	}
+}
@@ -0,0 +1,224 @@
+use crate::client::AocClient;
+use regex::Regex;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+pub fn submit_answer(
+	client: &AocClient,
+	year: u32,
+	day: u32,
+	part: u32,
+	answer: &str,
+) -> anyhow::Result<Verdict> {
+	let html = client.post_form(
+		&format!("/{year}/day/{day}/answer"),
+		&[("level", &part.to_string()), ("answer", answer)],
+		"submit answer",
+	)?;
+	Ok(parse_verdict(&html))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+	RateLimited {
+		wait: Option<Duration>,
+	},
+	/// Returned when submitting for a part that is either locked or already solved
+	WrongLevel,
+	/// Anything we failed to recognise, with the text of the response article
+	Unknown(String),
+}
+
+impl Display for Verdict {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Verdict::Correct => write!(f, "That's the right answer!"),
+			Verdict::Wrong => write!(f, "That's not the right answer."),
+			Verdict::TooHigh => write!(f, "That's not the right answer, your answer is too high."),
+			Verdict::TooLow => write!(f, "That's not the right answer, your answer is too low."),
+			Verdict::RateLimited { wait: Some(wait) } => write!(
+				f,
+				"You gave an answer too recently, wait {}m {}s before trying again.",
+				wait.as_secs() / 60,
+				wait.as_secs() % 60
+			),
+			Verdict::RateLimited { wait: None } => {
+				write!(
+					f,
+					"You gave an answer too recently, wait before trying again."
+				)
+			}
+			Verdict::WrongLevel => write!(
+				f,
+				"You don't seem to be solving the right level. Did you already complete it?"
+			),
+			Verdict::Unknown(text) => write!(f, "Unrecognized response: {}", text),
+		}
+	}
+}
+
+/// Parses the HTML page returned by `/{year}/day/{day}/answer`
+pub fn parse_verdict(html: &str) -> Verdict {
+	let text = article_text(html);
+
+	if text.contains("That's the right answer") {
+		Verdict::Correct
+	} else if text.contains("You gave an answer too recently") {
+		let wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")
+			.unwrap()
+			.captures(&text)
+			.map(|caps| {
+				let minutes = caps
+					.get(1)
+					.map(|m| m.as_str().parse::<u64>().unwrap())
+					.unwrap_or_default();
+				let seconds = caps[2].parse::<u64>().unwrap();
+				Duration::from_secs(minutes * 60 + seconds)
+			});
+		Verdict::RateLimited { wait }
+	} else if text.contains("That's not the right answer") {
+		if text.contains("your answer is too high") {
+			Verdict::TooHigh
+		} else if text.contains("your answer is too low") {
+			Verdict::TooLow
+		} else {
+			Verdict::Wrong
+		}
+	} else if text.contains("You don't seem to be solving the right level") {
+		Verdict::WrongLevel
+	} else {
+		Verdict::Unknown(text)
+	}
+}
+
+/// Extracts text of the first `<article>` element (or the whole document if there is none),
+/// with tags removed and whitespace collapsed.
+fn article_text(html: &str) -> String {
+	let article = html
+		.find("<article")
+		.and_then(|start| {
+			let end = html[start..].find("</article>")?;
+			Some(&html[start..(start + end)])
+		})
+		.unwrap_or(html);
+
+	let mut text = String::with_capacity(article.len());
+	let mut in_tag = false;
+	for c in article.chars() {
+		match c {
+			'<' => in_tag = true,
+			'>' => in_tag = false,
+			_ if !in_tag => text.push(c),
+			_ => (),
+		}
+	}
+
+	text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Verdict;
+	use crate::client::test_client;
+	use crate::fixture_server::{FixtureServer, FIXTURES_DIR};
+	use std::time::Duration;
+
+	const CORRECT: &str = r#"<main>
+<article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to restoring snow operations. <a href="/2023/day/1#part2">[Continue to Part Two]</a></p></article>
+</main>"#;
+
+	const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2023/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	const TOO_LOW: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	const WRONG: &str = r#"<main>
+<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	const RATE_LIMITED: &str = r#"<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	const RATE_LIMITED_SECONDS: &str = r#"<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 33s left to wait. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	const WRONG_LEVEL: &str = r#"<main>
+<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+	#[test]
+	fn parse_verdicts() {
+		assert_eq!(super::parse_verdict(CORRECT), Verdict::Correct);
+		assert_eq!(super::parse_verdict(TOO_HIGH), Verdict::TooHigh);
+		assert_eq!(super::parse_verdict(TOO_LOW), Verdict::TooLow);
+		assert_eq!(super::parse_verdict(WRONG), Verdict::Wrong);
+		assert_eq!(
+			super::parse_verdict(RATE_LIMITED),
+			Verdict::RateLimited {
+				wait: Some(Duration::from_secs(65))
+			}
+		);
+		assert_eq!(
+			super::parse_verdict(RATE_LIMITED_SECONDS),
+			Verdict::RateLimited {
+				wait: Some(Duration::from_secs(33))
+			}
+		);
+		assert_eq!(super::parse_verdict(WRONG_LEVEL), Verdict::WrongLevel);
+		assert!(matches!(
+			super::parse_verdict("<html>Something else</html>"),
+			Verdict::Unknown(text) if text == "Something else"
+		));
+	}
+
+	#[test]
+	fn submit_answer() {
+		let server = FixtureServer::start(|_request| (200, TOO_LOW.to_string())).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		let verdict = super::submit_answer(&client, 2023, 7, 2, "1234").unwrap();
+		assert_eq!(verdict, Verdict::TooLow);
+
+		let requests = server.requests();
+		assert_eq!(requests.len(), 1);
+		let request = &requests[0];
+		assert_eq!(request.method, "POST");
+		assert_eq!(request.path, "/2023/day/7/answer");
+		assert_eq!(request.header("cookie"), Some("session=abcd"));
+		assert_eq!(
+			request.header("content-type"),
+			Some("application/x-www-form-urlencoded")
+		);
+		assert_eq!(request.body, "level=2&answer=1234");
+	}
+
+	#[test]
+	fn submit_answer_to_fixture_server() {
+		let server = FixtureServer::serve_dir(0, FIXTURES_DIR).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		let verdict = super::submit_answer(&client, 2023, 1, 1, "142").unwrap();
+		assert_eq!(verdict, Verdict::Correct);
+	}
+
+	#[test]
+	fn submit_answer_server_error() {
+		let server = FixtureServer::start(|_request| (500, String::from("oops"))).unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let client = test_client(&server.addr(), cache_dir.path());
+
+		let err = super::submit_answer(&client, 2023, 7, 1, "1").unwrap_err();
+		assert!(err.to_string().contains("500"), "{err}");
+	}
+}
@@ -0,0 +1,81 @@
+//! Plain text tables for terminal output
+
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Align {
+	Left,
+	Right,
+}
+
+pub struct Table {
+	headers: Vec<(String, Align)>,
+	rows: Vec<Vec<String>>,
+}
+
+impl Table {
+	pub fn new<'a>(headers: impl IntoIterator<Item = (&'a str, Align)>) -> Self {
+		Table {
+			headers: headers
+				.into_iter()
+				.map(|(header, align)| (header.to_string(), align))
+				.collect(),
+			rows: Vec::new(),
+		}
+	}
+
+	pub fn push_row(&mut self, row: Vec<String>) {
+		self.rows.push(row);
+	}
+}
+
+impl Display for Table {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let widths: Vec<usize> = self
+			.headers
+			.iter()
+			.enumerate()
+			.map(|(idx, (header, _))| {
+				self.rows
+					.iter()
+					.filter_map(|row| row.get(idx))
+					.map(|cell| cell.chars().count())
+					.chain(std::iter::once(header.chars().count()))
+					.max()
+					.unwrap_or_default()
+			})
+			.collect();
+
+		let header_row: Vec<String> = self.headers.iter().map(|(h, _)| h.clone()).collect();
+		for row in std::iter::once(&header_row).chain(self.rows.iter()) {
+			let mut line = String::new();
+			for (idx, ((_, align), width)) in self.headers.iter().zip(&widths).enumerate() {
+				let cell = row.get(idx).map(String::as_str).unwrap_or_default();
+				if idx > 0 {
+					line.push_str("  ");
+				}
+				match align {
+					Align::Left => line.push_str(&format!("{:<width$}", cell, width = width)),
+					Align::Right => line.push_str(&format!("{:>width$}", cell, width = width)),
+				}
+			}
+			writeln!(f, "{}", line.trim_end())?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Align, Table};
+
+	#[test]
+	fn display() {
+		let mut table = Table::new([("Day", Align::Right), ("Answer", Align::Left)]);
+		table.push_row(vec![String::from("1"), String::from("54239")]);
+		table.push_row(vec![String::from("10"), String::from("7")]);
+
+		assert_eq!(table.to_string(), "Day  Answer\n  1  54239\n 10  7\n");
+	}
+}
@@ -0,0 +1,104 @@
+//! Puzzles unlock at midnight EST (UTC-5) on the 1st to the 25th of December.
+
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns when the puzzle of `day` of `year` unlocks
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+	let days = days_from_civil(year as i64, 12, day as i64);
+	let secs = days * 24 * 60 * 60 + 5 * 60 * 60;
+	UNIX_EPOCH + Duration::from_secs(secs as u64)
+}
+
+/// Time left until the puzzle unlocks, `None` once it's unlocked
+pub fn time_to_unlock(year: u32, day: u32, now: SystemTime) -> Option<Duration> {
+	unlock_time(year, day)
+		.duration_since(now)
+		.ok()
+		.filter(|left| !left.is_zero())
+}
+
+/// Shows a countdown on the terminal until the puzzle unlocks
+pub fn wait_for_unlock(year: u32, day: u32) {
+	let mut stdout = std::io::stdout();
+	while let Some(left) = time_to_unlock(year, day, SystemTime::now()) {
+		// Spaces clear what's left of a longer previous line
+		print!("\rDay {} unlocks in {}   ", day, format_countdown(left));
+		let _ = stdout.flush();
+		std::thread::sleep(left.min(Duration::from_secs(1)));
+	}
+	println!("\rDay {} is unlocked{}", day, " ".repeat(16));
+}
+
+/// Formats as `1d 02:03:04`, leaving out the days when there are none
+pub fn format_countdown(duration: Duration) -> String {
+	// Rounding up, so the countdown reaches 00:00:00 at the unlock instead of a second early
+	let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+	let (days, secs) = (secs / 86400, secs % 86400);
+	let hms = format!(
+		"{:02}:{:02}:{:02}",
+		secs / 3600,
+		secs % 3600 / 60,
+		secs % 60
+	);
+	match days {
+		0 => hms,
+		days => format!("{}d {}", days, hms),
+	}
+}
+
+/// Days since 1970-01-01 of a date of the proleptic Gregorian calendar, after Howard Hinnant's
+/// `days_from_civil`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, UNIX_EPOCH};
+
+	#[test]
+	fn unlock_time() {
+		// 2023-12-01T05:00:00Z
+		assert_eq!(
+			super::unlock_time(2023, 1),
+			UNIX_EPOCH + Duration::from_secs(1701406800)
+		);
+		// 2024-12-25T05:00:00Z, in a leap year
+		assert_eq!(
+			super::unlock_time(2024, 25),
+			UNIX_EPOCH + Duration::from_secs(1735102800)
+		);
+
+		let unlock = super::unlock_time(2023, 1);
+		assert_eq!(
+			super::time_to_unlock(2023, 1, unlock - Duration::from_secs(90)),
+			Some(Duration::from_secs(90))
+		);
+		assert_eq!(
+			super::time_to_unlock(2023, 1, unlock + Duration::from_secs(1)),
+			None
+		);
+	}
+
+	#[test]
+	fn format_countdown() {
+		assert_eq!(
+			super::format_countdown(Duration::from_millis(500)),
+			"00:00:01"
+		);
+		assert_eq!(
+			super::format_countdown(Duration::from_secs(3725)),
+			"01:02:05"
+		);
+		assert_eq!(
+			super::format_countdown(Duration::from_secs(2 * 86400 + 59)),
+			"2d 00:00:59"
+		);
+	}
+}
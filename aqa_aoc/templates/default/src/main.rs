@@ -0,0 +1,43 @@
+use aoc_lib::Solution;
+
+aoc_lib::main!(Day);
+
+struct Day;
+
+impl Solution for Day {
+	type Parsed<'a> = Vec<&'a str>;
+	type Answer = i64;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<&str>> {
+		Ok(input.lines().collect())
+	}
+
+	fn part_1(_lines: &Vec<&str>) -> anyhow::Result<i64> {
+		todo!("{{year}} day {{day}} part 1")
+	}
+
+	fn part_2(_lines: &Vec<&str>) -> anyhow::Result<i64> {
+		todo!("{{year}} day {{day}} part 2")
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
+	const EXAMPLE_1: &str = "";
+
+	#[test]
+	#[ignore = "example not filled in"]
+	fn part_1() {
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 0);
+	}
+
+	#[test]
+	#[ignore = "example not filled in"]
+	fn part_2() {
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 0);
+	}
+}
@@ -1,64 +1,55 @@
-#![allow(clippy::ptr_arg)]
-
 use anyhow::bail;
+use aoc_lib::grid::{Pos, DIRECTIONS_4};
+use aoc_lib::{Grid, Solution};
 use itertools::Itertools;
 use std::collections::{hash_map, HashMap, VecDeque};
 use std::ops::Div;
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_10/input.txt")?;
-	let (part_1, part_2) = solve(&input)?;
-	println!("Part 1: {part_1}");
-	println!("Part 2: {part_2}");
-	Ok(())
-}
+aoc_lib::main!(Day);
 
-fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
-	let grid: Vec<Vec<Tile>> = input
-		.lines()
-		.map(|line| line.bytes().map(Tile::try_from).try_collect())
-		.try_collect()?;
+struct Day;
 
-	let mut lengths = HashMap::new();
+impl Solution for Day {
+	type Parsed<'a> = Grid<Tile>;
+	type Answer = i64;
 
-	let starting_pos: (usize, usize) = grid
-		.iter()
-		.enumerate()
-		.find_map(|(y_idx, line)| {
-			line.iter()
-				.enumerate()
-				.find(|(_, tile)| **tile == Tile::StartingPos)
-				.map(|(x_idx, _)| (y_idx, x_idx))
-		})
-		.unwrap();
-
-	lengths.insert(starting_pos, 0);
-
-	let mut queue = VecDeque::new();
-	queue.push_back((starting_pos, 0));
-
-	while let Some((pos, length)) = queue.pop_front() {
-		let fns = [get_north, get_east, get_south, get_west];
-		for f in fns {
-			if let Some((next_pos, _next_tile)) = f(&grid, pos) {
+	fn parse(input: &str) -> anyhow::Result<Grid<Tile>> {
+		Grid::parse(input)
+	}
+
+	fn part_1(grid: &Grid<Tile>) -> anyhow::Result<i64> {
+		let starting_pos = starting_pos(grid)?;
+
+		let mut lengths = HashMap::new();
+		lengths.insert(starting_pos, 0);
+
+		let mut queue = VecDeque::new();
+		queue.push_back((starting_pos, 0));
+
+		while let Some((pos, length)) = queue.pop_front() {
+			for next_pos in connected(grid, pos) {
 				if let hash_map::Entry::Vacant(entry) = lengths.entry(next_pos) {
 					entry.insert(length + 1);
 					queue.push_back((next_pos, length + 1));
 				}
 			}
 		}
+
+		Ok(lengths.values().max().copied().unwrap())
 	}
 
-	let part_1 = lengths.values().max().copied().unwrap();
+	fn part_2(grid: &Grid<Tile>) -> anyhow::Result<i64> {
+		let starting_pos = starting_pos(grid)?;
 
-	lengths.clear();
-	lengths.insert(starting_pos, 0);
-	queue.push_back((starting_pos, 0));
+		let mut lengths = HashMap::new();
+		lengths.insert(starting_pos, 0);
 
-	'queue_loop: while let Some((pos, length)) = queue.pop_front() {
-		let fns = [get_north, get_east, get_south, get_west];
-		for f in fns {
-			if let Some((next_pos, _next_tile)) = f(&grid, pos) {
+		let mut queue = VecDeque::new();
+		queue.push_back((starting_pos, 0));
+
+		// Follows the loop in one direction only, so the points come out in polygon order
+		'queue_loop: while let Some((pos, length)) = queue.pop_front() {
+			for next_pos in connected(grid, pos) {
 				if let hash_map::Entry::Vacant(entry) = lengths.entry(next_pos) {
 					entry.insert(length + 1);
 					queue.push_back((next_pos, length + 1));
@@ -66,28 +57,33 @@ fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
 				}
 			}
 		}
-	}
 
-	let points: Vec<(usize, usize)> = lengths
-		.into_iter()
-		.sorted_by_key(|(_pos, length)| *length)
-		.map(|(pos, _)| pos)
-		.collect();
+		let points: Vec<Pos> = lengths
+			.into_iter()
+			.sorted_by_key(|(_pos, length)| *length)
+			.map(|(pos, _)| pos)
+			.collect();
 
-	let area = polygon_area(&points);
-	// Use the polygon area from shoelace formula to calculate number of internal points using
-	// Pick's Theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem)
-	let part_2 = internal_points(points.len() as i64, area);
+		let area = polygon_area(&points);
+		// Use the polygon area from shoelace formula to calculate number of internal points using
+		// Pick's Theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem)
+		Ok(internal_points(points.len() as i64, area))
+	}
+}
 
-	Ok((part_1, part_2))
+fn starting_pos(grid: &Grid<Tile>) -> anyhow::Result<Pos> {
+	match grid.find(|tile| *tile == Tile::StartingPos) {
+		Some(pos) => Ok(pos),
+		None => bail!("no starting position"),
+	}
 }
 
 // https://en.wikipedia.org/wiki/Shoelace_formula
-fn polygon_area(points: &[(usize, usize)]) -> i64 {
+fn polygon_area(points: &[Pos]) -> i64 {
 	points
 		.iter()
 		.zip(points.iter().cycle().skip(points.len() - 1))
-		.map(|((i_y, i_x), (j_y, j_x))| (*j_x as i64 + *i_x as i64) * (*j_y as i64 - *i_y as i64))
+		.map(|((i_x, i_y), (j_x, j_y))| (*j_x as i64 + *i_x as i64) * (*j_y as i64 - *i_y as i64))
 		.sum::<i64>()
 		.div(2)
 		.abs()
@@ -97,86 +93,17 @@ fn internal_points(boundary_points: i64, polygon_area: i64) -> i64 {
 	-(boundary_points / 2) + 1 + polygon_area
 }
 
-fn get_north(
-	grid: &Vec<Vec<Tile>>,
-	(pos_y, pos_x): (usize, usize),
-) -> Option<((usize, usize), Tile)> {
-	if pos_y == 0
-		|| matches!(
-			grid[pos_y][pos_x],
-			Tile::Horizontal | Tile::BendSouthWest | Tile::BendSouthEast | Tile::Ground
-		) {
-		return None;
-	}
-	let tile = grid[pos_y - 1][pos_x];
-	if matches!(
-		tile,
-		Tile::Horizontal | Tile::BendNorthEast | Tile::BendNorthWest | Tile::Ground
-	) {
-		return None;
-	}
-	Some(((pos_y - 1, pos_x), tile))
-}
-
-fn get_east(
-	grid: &Vec<Vec<Tile>>,
-	(pos_y, pos_x): (usize, usize),
-) -> Option<((usize, usize), Tile)> {
-	if matches!(
-		grid[pos_y][pos_x],
-		Tile::Vertical | Tile::BendSouthWest | Tile::BendNorthWest | Tile::Ground
-	) {
-		return None;
-	}
-	let tile = *grid[pos_y].get(pos_x + 1)?;
-	if matches!(
-		tile,
-		Tile::Vertical | Tile::BendNorthEast | Tile::BendSouthEast | Tile::Ground
-	) {
-		return None;
-	}
-	Some(((pos_y, pos_x + 1), tile))
-}
-
-fn get_south(
-	grid: &Vec<Vec<Tile>>,
-	(pos_y, pos_x): (usize, usize),
-) -> Option<((usize, usize), Tile)> {
-	if matches!(
-		grid[pos_y][pos_x],
-		Tile::Horizontal | Tile::BendNorthEast | Tile::BendNorthWest | Tile::Ground
-	) {
-		return None;
-	}
-	let tile = grid.get(pos_y + 1)?[pos_x];
-	if matches!(
-		tile,
-		Tile::Horizontal | Tile::BendSouthEast | Tile::BendSouthWest | Tile::Ground
-	) {
-		return None;
-	}
-	Some(((pos_y + 1, pos_x), tile))
-}
-
-fn get_west(
-	grid: &Vec<Vec<Tile>>,
-	(pos_y, pos_x): (usize, usize),
-) -> Option<((usize, usize), Tile)> {
-	if pos_x == 0
-		|| matches!(
-			grid[pos_y][pos_x],
-			Tile::Vertical | Tile::BendNorthEast | Tile::BendSouthEast | Tile::Ground
-		) {
-		return None;
-	}
-	let tile = grid[pos_y][pos_x - 1];
-	if matches!(
-		tile,
-		Tile::Vertical | Tile::BendNorthWest | Tile::BendSouthWest | Tile::Ground
-	) {
-		return None;
-	}
-	Some(((pos_y, pos_x - 1), tile))
+/// Neighbours of `pos` its pipe connects to, which have to connect back
+fn connected(grid: &Grid<Tile>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+	let openings = grid[pos].openings();
+	(0..4).filter_map(move |dir| {
+		if !openings[dir] {
+			return None;
+		}
+		let next_pos = grid.offset(pos, DIRECTIONS_4[dir])?;
+		// The opposite direction
+		grid[next_pos].openings()[(dir + 2) % 4].then_some(next_pos)
+	})
 }
 
 #[allow(dead_code)]
@@ -193,12 +120,29 @@ enum Tile {
 	StartingPos = b'S',
 }
 
+impl Tile {
+	/// Whether the tile connects north, east, south and west, in the order of [`DIRECTIONS_4`].
+	/// The starting position connects anywhere, as its pipe is hidden.
+	fn openings(self) -> [bool; 4] {
+		match self {
+			Tile::Vertical => [true, false, true, false],
+			Tile::Horizontal => [false, true, false, true],
+			Tile::BendNorthEast => [true, true, false, false],
+			Tile::BendNorthWest => [true, false, false, true],
+			Tile::BendSouthWest => [false, false, true, true],
+			Tile::BendSouthEast => [false, true, true, false],
+			Tile::Ground => [false; 4],
+			Tile::StartingPos => [true; 4],
+		}
+	}
+}
+
 impl TryFrom<u8> for Tile {
 	type Error = anyhow::Error;
 
 	fn try_from(value: u8) -> Result<Self, Self::Error> {
 		if matches!(value, b'|' | b'-' | b'L' | b'J' | b'7' | b'F' | b'.' | b'S') {
-			Ok(unsafe { std::mem::transmute(value) })
+			Ok(unsafe { std::mem::transmute::<u8, Tile>(value) })
 		} else {
 			bail!("invalid tile value {}", value as char)
 		}
@@ -207,6 +151,9 @@ impl TryFrom<u8> for Tile {
 
 #[cfg(test)]
 mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = ".....
 .S-7.
 .|.|.
@@ -237,22 +184,22 @@ LJ.LJ
 
 	#[test]
 	fn part_1_example_1() {
-		assert_eq!(super::solve(EXAMPLE_1).unwrap().0, 4);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 4);
 	}
 
 	#[test]
 	fn part_1_example_2() {
-		assert_eq!(super::solve(EXAMPLE_2).unwrap().0, 4);
+		assert_eq!(Day::solve_part_1(EXAMPLE_2).unwrap(), 4);
 	}
 
 	#[test]
 	fn part_1_example_3() {
-		assert_eq!(super::solve(EXAMPLE_3).unwrap().0, 8);
+		assert_eq!(Day::solve_part_1(EXAMPLE_3).unwrap(), 8);
 	}
 
 	#[test]
 	fn part_1_example_4() {
-		assert_eq!(super::solve(EXAMPLE_4).unwrap().0, 8);
+		assert_eq!(Day::solve_part_1(EXAMPLE_4).unwrap(), 8);
 	}
 
 	const EXAMPLE_5: &str = "...........
@@ -303,26 +250,26 @@ L7JLJL-JLJLJL--JLJ.L
 
 	#[test]
 	fn part_2_example_1() {
-		assert_eq!(super::solve(EXAMPLE_1).unwrap().1, 1);
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 1);
 	}
 
 	#[test]
 	fn part_2_example_5() {
-		assert_eq!(super::solve(EXAMPLE_5).unwrap().1, 4);
+		assert_eq!(Day::solve_part_2(EXAMPLE_5).unwrap(), 4);
 	}
 
 	#[test]
 	fn part_2_example_6() {
-		assert_eq!(super::solve(EXAMPLE_6).unwrap().1, 4);
+		assert_eq!(Day::solve_part_2(EXAMPLE_6).unwrap(), 4);
 	}
 
 	#[test]
 	fn part_2_example_7() {
-		assert_eq!(super::solve(EXAMPLE_7).unwrap().1, 8);
+		assert_eq!(Day::solve_part_2(EXAMPLE_7).unwrap(), 8);
 	}
 
 	#[test]
 	fn part_2_example_8() {
-		assert_eq!(super::solve(EXAMPLE_8).unwrap().1, 10);
+		assert_eq!(Day::solve_part_2(EXAMPLE_8).unwrap(), 10);
 	}
 }
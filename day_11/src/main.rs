@@ -1,63 +1,78 @@
+use aoc_lib::{Grid, Solution};
 use itertools::Itertools;
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_11/input.txt")?;
-	let (part_1, part_2) = solve(&input)?;
-	println!("Part 1: {part_1}");
-	println!("Part 2: {part_2}");
-	Ok(())
-}
+aoc_lib::main!(Day);
 
-fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
-	let grid: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
-
-	// Rows and columns converted to true if they have a galaxy and false otherwise
-	let rows: Vec<bool> = grid
-		.iter()
-		.map(|row| row.iter().any(|c| *c == b'#'))
-		.collect();
-	let columns: Vec<bool> = (0..grid[0].len())
-		.map(|column_idx| grid.iter().any(|row| row[column_idx] == b'#'))
-		.collect();
-
-	let mut galaxies = Vec::new();
-	for (y, row) in grid.iter().enumerate() {
-		for (x, b) in row.iter().enumerate() {
-			if *b == b'#' {
-				galaxies.push((x, y));
-			}
-		}
+struct Day;
+
+impl Solution for Day {
+	type Parsed<'a> = Image;
+	type Answer = i64;
+
+	fn parse(input: &str) -> anyhow::Result<Image> {
+		let grid: Grid<u8> = Grid::parse(input)?;
+
+		// Rows and columns converted to true if they have a galaxy and false otherwise
+		let rows: Vec<bool> = grid.rows().map(|row| row.contains(&b'#')).collect();
+		let columns: Vec<bool> = grid
+			.columns()
+			.map(|mut column| column.any(|b| *b == b'#'))
+			.collect();
+
+		let galaxies = grid
+			.iter()
+			.filter(|(_, b)| **b == b'#')
+			.map(|(pos, _)| pos)
+			.collect();
+
+		Ok(Image {
+			galaxies,
+			rows,
+			columns,
+		})
+	}
+
+	fn part_1(image: &Image) -> anyhow::Result<i64> {
+		Ok(distance_sum(image, 2))
+	}
+
+	fn part_2(image: &Image) -> anyhow::Result<i64> {
+		Ok(distance_sum(image, 1_000_000))
 	}
+}
 
-	let (mut sum_p1, mut sum_p2) = (0, 0);
-	for (a, b) in galaxies.iter().copied().tuple_combinations() {
+struct Image {
+	galaxies: Vec<(usize, usize)>,
+	rows: Vec<bool>,
+	columns: Vec<bool>,
+}
+
+/// Sums distances between all pairs of galaxies, with every empty row and column replaced by
+/// `expansion` of them
+fn distance_sum(image: &Image, expansion: usize) -> i64 {
+	let mut sum = 0;
+	for (mut a, mut b) in image.galaxies.iter().copied().tuple_combinations() {
 		let x_expand = (a.0.min(b.0)..a.0.max(b.0))
-			.filter(|x| !columns[*x])
+			.filter(|x| !image.columns[*x])
+			.count();
+		let y_expand = (a.1.min(b.1)..a.1.max(b.1))
+			.filter(|y| !image.rows[*y])
 			.count();
-		let y_expand = (a.1.min(b.1)..a.1.max(b.1)).filter(|y| !rows[*y]).count();
 
-		let (mut a_p1, mut b_p1) = (a, b);
-		let (mut a_p2, mut b_p2) = (a, b);
 		if a.0 > b.0 {
-			a_p1.0 += x_expand;
-			a_p2.0 += x_expand * (1_000_000 - 1);
+			a.0 += x_expand * (expansion - 1);
 		} else {
-			b_p1.0 += x_expand;
-			b_p2.0 += x_expand * (1_000_000 - 1);
+			b.0 += x_expand * (expansion - 1);
 		}
 		if a.1 > b.1 {
-			a_p1.1 += y_expand;
-			a_p2.1 += y_expand * (1_000_000 - 1);
+			a.1 += y_expand * (expansion - 1);
 		} else {
-			b_p1.1 += y_expand;
-			b_p2.1 += y_expand * (1_000_000 - 1);
+			b.1 += y_expand * (expansion - 1);
 		}
 
-		sum_p1 += manhattan_distance(a_p1, b_p1);
-		sum_p2 += manhattan_distance(a_p2, b_p2);
+		sum += manhattan_distance(a, b);
 	}
-
-	Ok((sum_p1, sum_p2))
+	sum
 }
 
 fn manhattan_distance((x1, y1): (usize, usize), (x2, y2): (usize, usize)) -> i64 {
@@ -66,6 +81,9 @@ fn manhattan_distance((x1, y1): (usize, usize), (x2, y2): (usize, usize)) -> i64
 
 #[cfg(test)]
 mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = "...#......
 .......#..
 #.........
@@ -80,6 +98,13 @@ mod tests {
 
 	#[test]
 	fn part_1() {
-		assert_eq!(super::solve(EXAMPLE_1).unwrap().0, 374);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 374);
+	}
+
+	#[test]
+	fn part_2() {
+		let image = Day::parse(EXAMPLE_1).unwrap();
+		assert_eq!(super::distance_sum(&image, 10), 1030);
+		assert_eq!(super::distance_sum(&image, 100), 8410);
 	}
 }
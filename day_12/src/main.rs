@@ -1,75 +1,35 @@
 use anyhow::bail;
+use aoc_lib::Solution;
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Write};
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_12/input.txt")?;
-	// println!("Part 1: {}", part_1(&input)?);
-	println!("Part 2: {}", part_2(&input)?);
-	Ok(())
-}
-
-fn part_1(input: &str) -> anyhow::Result<i64> {
-	let lines: Vec<(Vec<Spring>, Vec<i64>)> = input
-		.lines()
-		.map(|line| {
-			let (springs, group_sizes) = line.split(' ').next_tuple().unwrap();
-			let springs = springs.chars().map(Spring::try_from).try_collect()?;
-			let group_sizes = group_sizes
-				.split(',')
-				.map(|x| x.parse::<i64>())
-				.try_collect()?;
-			Ok::<_, anyhow::Error>((springs, group_sizes))
-		})
-		.try_collect()?;
+aoc_lib::main!(Day);
 
-	let mut out = 0;
-	let mut spring_idx = 0;
-	for (spring, sizes) in lines {
-		println!("{spring_idx}");
-		spring_idx += 1;
+struct Day;
 
-		let unknowns: Vec<usize> = spring
-			.iter()
-			.enumerate()
-			.filter(|(_, s)| **s == Spring::Unknown)
-			.map(|(idx, _)| idx)
-			.collect();
+impl Solution for Day {
+	type Parsed<'a> = Vec<(Vec<Spring>, Vec<i64>)>;
+	type Answer = i64;
 
-		out += solve(spring, &unknowns, &sizes);
+	fn parse(input: &str) -> anyhow::Result<Vec<(Vec<Spring>, Vec<i64>)>> {
+		input
+			.lines()
+			.map(|line| {
+				let (springs, group_sizes) = line.split(' ').next_tuple().unwrap();
+				let springs = springs.chars().map(Spring::try_from).try_collect()?;
+				let group_sizes = group_sizes
+					.split(',')
+					.map(|x| x.parse::<i64>())
+					.try_collect()?;
+				Ok::<_, anyhow::Error>((springs, group_sizes))
+			})
+			.try_collect()
 	}
 
-	Ok(out)
-}
-
-fn part_2(input: &str) -> anyhow::Result<i64> {
-	let lines: Vec<(Vec<Spring>, Vec<i64>)> = input
-		.lines()
-		.map(|line| {
-			let (springs, group_sizes) = line.split(' ').next_tuple().unwrap();
-			let mut springs: Vec<Spring> = springs.chars().map(Spring::try_from).try_collect()?;
-			let orig_springs = springs.clone();
-			for _ in 0..4 {
-				springs.push(Spring::Unknown);
-				springs.extend_from_slice(orig_springs.as_slice());
-			}
-
-			let group_sizes = group_sizes
-				.split(',')
-				.map(|x| x.parse::<i64>())
-				.try_collect::<_, Vec<i64>, _>()?
-				.repeat(5);
-			Ok::<_, anyhow::Error>((springs, group_sizes))
-		})
-		.try_collect()?;
-
-	// let mut out = 0;
-	let out = lines
-		.into_par_iter()
-		.enumerate()
-		.map(|(idx, (spring, sizes))| {
+	fn part_1(records: &Vec<(Vec<Spring>, Vec<i64>)>) -> anyhow::Result<i64> {
+		let mut out = 0;
+		for (spring, sizes) in records.iter().cloned() {
 			let unknowns: Vec<usize> = spring
 				.iter()
 				.enumerate()
@@ -77,13 +37,42 @@ fn part_2(input: &str) -> anyhow::Result<i64> {
 				.map(|(idx, _)| idx)
 				.collect();
 
-			let r = solve(spring, &unknowns, &sizes);
-			println!("{idx} = {r}");
-			r
-		})
-		.sum();
+			out += solve(spring, &unknowns, &sizes);
+		}
+
+		Ok(out)
+	}
+
+	fn part_2(records: &Vec<(Vec<Spring>, Vec<i64>)>) -> anyhow::Result<i64> {
+		let lines: Vec<(Vec<Spring>, Vec<i64>)> = records
+			.iter()
+			.map(|(orig_springs, group_sizes)| {
+				let mut springs = orig_springs.clone();
+				for _ in 0..4 {
+					springs.push(Spring::Unknown);
+					springs.extend_from_slice(orig_springs.as_slice());
+				}
+
+				(springs, group_sizes.repeat(5))
+			})
+			.collect();
+
+		let out = lines
+			.into_par_iter()
+			.map(|(spring, sizes)| {
+				let unknowns: Vec<usize> = spring
+					.iter()
+					.enumerate()
+					.filter(|(_, s)| **s == Spring::Unknown)
+					.map(|(idx, _)| idx)
+					.collect();
+
+				solve(spring, &unknowns, &sizes)
+			})
+			.sum();
 
-	Ok(out)
+		Ok(out)
+	}
 }
 
 fn solve(mut spring: Vec<Spring>, unknowns: &[usize], sizes: &[i64]) -> i64 {
@@ -91,7 +80,8 @@ fn solve(mut spring: Vec<Spring>, unknowns: &[usize], sizes: &[i64]) -> i64 {
 	if spring
 		.iter()
 		.filter(|s| matches!(**s, Spring::Damaged | Spring::Unknown))
-		.count() < damaged_required as usize
+		.count()
+		< damaged_required as usize
 	{
 		return 0;
 	}
@@ -203,8 +193,7 @@ fn already_invalid(spring: &[Spring], sizes: &[i64]) -> bool {
 
 		spring_idx += 1;
 	}
-	let r = current_size_idx != sizes.len();
-	r
+	current_size_idx != sizes.len()
 }
 
 // 01234
@@ -239,6 +228,9 @@ impl Debug for Spring {
 
 #[cfg(test)]
 mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = "???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
@@ -249,16 +241,16 @@ mod tests {
 
 	#[test]
 	fn part_1_example_1() {
-		assert_eq!(super::part_1(EXAMPLE_1).unwrap(), 21);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 21);
 	}
 
 	#[test]
 	fn part_2_example_1() {
-		assert_eq!(super::part_2(EXAMPLE_1).unwrap(), 525152);
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 525152);
 	}
 
 	#[test]
 	fn difficult_example() {
-		assert_eq!(super::part_2("?###???????? 3,2,1").unwrap(), 506250);
+		assert_eq!(Day::solve_part_2("?###???????? 3,2,1").unwrap(), 506250);
 	}
 }
@@ -1,96 +1,100 @@
-#![allow(clippy::needless_range_loop)]
-
+use aoc_lib::grid::Pos;
+use aoc_lib::{Grid, Solution};
 use std::collections::HashMap;
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_3/input.txt")?;
-	let (part_1, part_2) = solve(&input)?;
-	println!("Part 1: {}", part_1);
-	println!("Part 2: {}", part_2);
-	Ok(())
-}
+aoc_lib::main!(Day);
 
-fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
-	let schematic: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+struct Day;
 
-	let mut nums = HashMap::<(usize, usize), Vec<u8>>::new();
+impl Solution for Day {
+	type Parsed<'a> = Schematic;
+	type Answer = i64;
 
-	for y in 0..schematic.len() {
-		let mut x = 0;
-		while x < schematic[y].len() {
-			let b = schematic[y][x];
-			if !b.is_ascii_digit() {
-				x += 1;
-				continue;
-			}
+	fn parse(input: &str) -> anyhow::Result<Schematic> {
+		let grid: Grid<u8> = Grid::parse(input)?;
+
+		let mut numbers = Vec::new();
+		for (y, row) in grid.rows().enumerate() {
+			let mut x = 0;
+			while x < row.len() {
+				if !row[x].is_ascii_digit() {
+					x += 1;
+					continue;
+				}
 
-			let end_idx = schematic[y]
-				.iter()
-				.enumerate()
-				.skip(x)
-				.find(|(_, b)| !b.is_ascii_digit())
-				.map(|(idx, _)| idx)
-				.unwrap_or(schematic[y].len());
-			let number = schematic[y][x..end_idx].to_vec();
-			nums.insert((y, x), number);
-			x = end_idx;
+				let end_idx = row[x..]
+					.iter()
+					.position(|b| !b.is_ascii_digit())
+					.map_or(row.len(), |len| x + len);
+				numbers.push(Number {
+					pos: (x, y),
+					len: end_idx - x,
+					value: std::str::from_utf8(&row[x..end_idx])?.parse::<i64>()?,
+				});
+				x = end_idx;
+			}
 		}
+
+		Ok(Schematic { grid, numbers })
 	}
 
-	let mut gears = HashMap::<(usize, usize), Vec<i64>>::new();
+	fn part_1(schematic: &Schematic) -> anyhow::Result<i64> {
+		Ok(schematic
+			.numbers
+			.iter()
+			.filter(|number| {
+				schematic
+					.neighbours(number)
+					.any(|pos| !matches!(schematic.grid[pos], b'0'..=b'9' | b'.'))
+			})
+			.map(|number| number.value)
+			.sum())
+	}
 
-	let mut part_1 = 0;
-	for ((y, x), number) in nums {
-		let n = std::str::from_utf8(&number).unwrap().parse::<i64>()?;
-		// (y, x)
-		let dirs = [
-			(-1, -1),
-			(-1, 0),
-			(-1, 1),
-			(0, 1),
-			(1, 1),
-			(1, 0),
-			(1, -1),
-			(0, -1),
-		];
-		let mut matched = false;
-		'current_num: for b_idx in 0..number.len() {
-			let x = x + b_idx;
-			for (d_y, d_x) in dirs {
-				let (new_y, new_x) = (y as isize + d_y, x as isize + d_x);
-				if new_y < 0
-					|| new_y >= schematic.len() as isize
-					|| new_x < 0 || new_x >= schematic[0].len() as isize
-				{
-					continue;
-				}
-				let (new_y, new_x) = (new_y as usize, new_x as usize);
-				let b = schematic[new_y][new_x];
-				if !matches!(b, b'0'..=b'9' | b'.') {
-					matched = true;
-				}
-				if b == b'*' {
-					gears.entry((new_y, new_x)).or_default().push(n);
-					break 'current_num;
-				}
+	fn part_2(schematic: &Schematic) -> anyhow::Result<i64> {
+		let mut gears = HashMap::<Pos, Vec<i64>>::new();
+		for number in &schematic.numbers {
+			if let Some(gear) = schematic
+				.neighbours(number)
+				.find(|pos| schematic.grid[*pos] == b'*')
+			{
+				gears.entry(gear).or_default().push(number.value);
 			}
 		}
-		if matched {
-			part_1 += n;
-		}
+
+		Ok(gears
+			.into_values()
+			.filter(|nums| nums.len() == 2)
+			.map(|nums| nums.into_iter().product::<i64>())
+			.sum::<i64>())
 	}
+}
 
-	let part_2 = gears
-		.into_values()
-		.filter(|nums| nums.len() == 2)
-		.map(|nums| nums.into_iter().product::<i64>())
-		.sum::<i64>();
+struct Schematic {
+	grid: Grid<u8>,
+	numbers: Vec<Number>,
+}
 
-	Ok((part_1, part_2))
+struct Number {
+	/// Position of the first digit
+	pos: Pos,
+	len: usize,
+	value: i64,
+}
+
+impl Schematic {
+	/// Positions around the digits of `number`, some of them more than once
+	fn neighbours<'a>(&'a self, number: &'a Number) -> impl Iterator<Item = Pos> + 'a {
+		let (x, y) = number.pos;
+		(0..number.len).flat_map(move |b_idx| self.grid.neighbours_8((x + b_idx, y)))
+	}
 }
 
 #[cfg(test)]
 mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = "467..114..
 ...*......
 ..35..633.
@@ -103,8 +107,7 @@ mod tests {
 .664.598..";
 	#[test]
 	fn test() {
-		let (part_1, part_2) = super::solve(EXAMPLE_1).unwrap();
-		assert_eq!(part_1, 4361);
-		assert_eq!(part_2, 467835);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 4361);
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 467835);
 	}
 }
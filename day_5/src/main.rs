@@ -1,33 +1,171 @@
-use std::ops::Range;
+use anyhow::{bail, Context};
+use aoc_lib::{RangeMap, RangeSet, Solution};
+use itertools::Itertools;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
-use itertools::Itertools;
+aoc_lib::main!(Day);
+
+struct Day;
+
+impl Solution for Day {
+	type Parsed<'a> = Almanac;
+	type Answer = i64;
+
+	fn parse(input: &str) -> anyhow::Result<Almanac> {
+		input.parse()
+	}
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_5/input.txt")?;
-	println!("Part 1: {}", part_1(&input)?);
-	println!("Part 2: {}", part_2(&input)?);
-	Ok(())
+	fn part_1(almanac: &Almanac) -> anyhow::Result<i64> {
+		let pipeline = almanac.pipeline();
+		almanac
+			.seeds
+			.iter()
+			.map(|seed| pipeline.get(*seed))
+			.min()
+			.context("no seeds")
+	}
+
+	fn part_2(almanac: &Almanac) -> anyhow::Result<i64> {
+		almanac
+			.pipeline()
+			.map_set(&almanac.seed_ranges())
+			.min()
+			.context("no seeds")
+	}
+
+	/// `--seed-for=L` prints the lowest seed of the seed ranges ending up at location `L`,
+	/// `--print-pipeline` the whole pipeline as a single map
+	fn run_option(option: &str, almanac: &Almanac) -> anyhow::Result<bool> {
+		if option == "--print-pipeline" {
+			print!("{}", almanac);
+		} else if let Some(location) = option.strip_prefix("--seed-for=") {
+			let location = location.parse::<i64>()?;
+			match almanac.seeds_for(location).min() {
+				Some(seed) => println!("Seed {} ends up at {}", seed, location),
+				None => println!("No seed ends up at {}", location),
+			}
+		} else {
+			return Ok(false);
+		}
+		Ok(true)
+	}
+}
+
+#[derive(Debug)]
+struct Almanac {
+	seeds: Vec<i64>,
+	/// Each converting the category the previous one converts to
+	stages: Vec<Stage>,
 }
 
-fn part_1(input: &str) -> anyhow::Result<i64> {
-	let input = parse_input(input)?;
+/// An `X-to-Y map:` section
+#[derive(Debug)]
+struct Stage {
+	source: String,
+	destination: String,
+	entries: Vec<MapEntry>,
+}
 
-	let to_soil = convert(&input.seeds, &input.seed_to_soil);
-	let to_fertilizer = convert(&to_soil, &input.soil_to_fertilizer);
-	let to_water = convert(&to_fertilizer, &input.fertilizer_to_water);
-	let to_light = convert(&to_water, &input.water_to_light);
-	let to_temperature = convert(&to_light, &input.light_to_temperature);
-	let to_humidity = convert(&to_temperature, &input.temperature_to_humidity);
-	let to_location = convert(&to_humidity, &input.humidity_to_location);
+impl Almanac {
+	/// All stages composed into one function, from the first category to the last
+	fn pipeline(&self) -> RangeMap {
+		self.stages.iter().fold(RangeMap::new(), |pipeline, stage| {
+			pipeline.then(&range_map(&stage.entries))
+		})
+	}
+
+	/// The seeds read as pairs of range start and length, as in part 2
+	fn seed_ranges(&self) -> RangeSet {
+		self.seeds
+			.iter()
+			.tuples()
+			.map(|(start, len)| *start..(*start + len))
+			.collect()
+	}
+
+	/// Seeds of the seed ranges ending up as `value` of the last category
+	fn seeds_for(&self, value: i64) -> RangeSet {
+		self.pipeline()
+			.preimage(&RangeSet::from(value..value + 1))
+			.intersection(&self.seed_ranges())
+	}
+}
+
+/// The whole pipeline as a single map in the format of the almanac, like `seed-to-location map:`
+impl Display for Almanac {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match (self.stages.first(), self.stages.last()) {
+			(Some(first), Some(last)) => {
+				writeln!(f, "{}-to-{} map:", first.source, last.destination)?
+			}
+			_ => writeln!(f, "identity map:")?,
+		}
+		for (source, offset) in self.pipeline().pieces() {
+			writeln!(
+				f,
+				"{} {} {}",
+				source.start + offset,
+				source.start,
+				source.end - source.start
+			)?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for Almanac {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut lines = s.lines();
+		let seeds: Vec<i64> = lines
+			.next()
+			.and_then(|line| line.strip_prefix("seeds:"))
+			.context("expected \"seeds:\" on the first line")?
+			.split_whitespace()
+			.map(|n| n.parse::<i64>())
+			.try_collect()?;
+
+		let mut stages: Vec<Stage> = Vec::new();
+		for line in lines.filter(|line| !line.is_empty()) {
+			if let Some(header) = line.strip_suffix(" map:") {
+				let (source, destination) = header
+					.split_once("-to-")
+					.with_context(|| format!("invalid map header \"{}\"", line))?;
+				if let Some(previous) = stages.last() {
+					if previous.destination != source {
+						bail!(
+							"\"{}\" doesn't follow the {} of the previous map",
+							line,
+							previous.destination
+						);
+					}
+				}
+				stages.push(Stage {
+					source: source.to_string(),
+					destination: destination.to_string(),
+					entries: Vec::new(),
+				});
+			} else {
+				stages
+					.last_mut()
+					.with_context(|| format!("\"{}\" before the first map header", line))?
+					.entries
+					.push(line.parse()?);
+			}
+		}
 
-	Ok(to_location.into_iter().min().unwrap())
+		Ok(Almanac { seeds, stages })
+	}
 }
 
-fn convert(src: &[i64], range_map: &[RangeMap]) -> Vec<i64> {
+/// Converts values point by point, the reference for [`range_map`]
+#[cfg(test)]
+fn convert(src: &[i64], range_map: &[MapEntry]) -> Vec<i64> {
 	src.iter()
 		.map(|seed| {
-			for RangeMap {
+			for MapEntry {
 				dst_range_start,
 				src_range_start,
 				len,
@@ -45,146 +183,35 @@ fn convert(src: &[i64], range_map: &[RangeMap]) -> Vec<i64> {
 		.collect()
 }
 
-fn part_2(input: &str) -> anyhow::Result<i64> {
-	let input = parse_input(input)?;
-	let mut seeds = Vec::new();
-	for (start, len) in input.seeds.iter().tuples() {
-		seeds.push(*start..(*start + len));
-	}
-
-	let to_soil = convert_range(&seeds, &input.seed_to_soil);
-	let to_fertilizer = convert_range(&to_soil, &input.soil_to_fertilizer);
-	let to_water = convert_range(&to_fertilizer, &input.fertilizer_to_water);
-	let to_light = convert_range(&to_water, &input.water_to_light);
-	let to_temperature = convert_range(&to_light, &input.light_to_temperature);
-	let to_humidity = convert_range(&to_temperature, &input.temperature_to_humidity);
-	let to_location = convert_range(&to_humidity, &input.humidity_to_location);
-
-	Ok(to_location
-		.into_iter()
-		.map(|range| range.min().unwrap())
-		.min()
-		.unwrap())
-}
-
-fn convert_range(src: &[Range<i64>], range_map: &[RangeMap]) -> Vec<Range<i64>> {
-	let mut out = Vec::new();
-	for range in src {
-		let mut ranges = Vec::new();
-		let mut range = range.clone();
-
-		for RangeMap {
-			dst_range_start,
-			src_range_start,
-			len,
-		} in range_map.into_iter().copied()
-		{
-			if range.start >= src_range_start && range.start < (src_range_start + len)
-				|| range.end <= (src_range_start + len) && range.end > src_range_start
-			{
-				let dst_range_idx = range.start - src_range_start;
-				let new_range_start = dst_range_start + dst_range_idx;
-				let new_range_end = new_range_start + (range.end - range.start);
-
-				let new_range =
-					new_range_start.max(dst_range_start)..new_range_end.min(dst_range_start + len);
-
-				let dangling_range_right = (src_range_start + len)..range.end;
-				let dangling_range_left = range.start..src_range_start;
-
-				out.push(new_range);
-
-				match (
-					dangling_range_left.is_empty(),
-					dangling_range_right.is_empty(),
-				) {
-					(true, true) => {
-						range = 0..0;
-						break;
-					}
-					(true, false) => {
-						ranges.push(dangling_range_right.clone());
-						range = dangling_range_right;
-					}
-					(false, true) => {
-						ranges.push(dangling_range_left.clone());
-						range = dangling_range_left;
-					}
-					(false, false) => panic!(),
-				}
-			}
-		}
-		if !range.is_empty() {
-			ranges.push(range.clone());
-		}
-		out.extend(ranges.into_iter());
+/// The conversion of a map as a function
+fn range_map(entries: &[MapEntry]) -> RangeMap {
+	let mut range_map = RangeMap::new();
+	for entry in entries {
+		range_map.insert(
+			entry.src_range_start..(entry.src_range_start + entry.len),
+			entry.dst_range_start,
+		);
 	}
-	out
-}
-
-fn parse_input(input: &str) -> anyhow::Result<Input> {
-	let mut lines = input.lines();
-	let seeds: Vec<i64> = lines
-		.next()
-		.unwrap()
-		.split(": ")
-		.nth(1)
-		.unwrap()
-		.split(" ")
-		.map(|n| n.parse::<i64>())
-		.try_collect()?;
-	lines.next().unwrap();
-	lines.next().unwrap();
-
-	let mut parse_map = || {
-		let mut range_maps = Vec::new();
-		while let Some(line) = lines.next() {
-			if line == "" {
-				lines.next().unwrap();
-				break;
-			}
-			range_maps.push(line.parse::<RangeMap>()?);
-		}
-		Ok::<Vec<_>, anyhow::Error>(range_maps)
-	};
-
-	Ok(Input {
-		seeds,
-		seed_to_soil: parse_map()?,
-		soil_to_fertilizer: parse_map()?,
-		fertilizer_to_water: parse_map()?,
-		water_to_light: parse_map()?,
-		light_to_temperature: parse_map()?,
-		temperature_to_humidity: parse_map()?,
-		humidity_to_location: parse_map()?,
-	})
-}
-
-#[derive(Debug)]
-struct Input {
-	seeds: Vec<i64>,
-	seed_to_soil: Vec<RangeMap>,
-	soil_to_fertilizer: Vec<RangeMap>,
-	fertilizer_to_water: Vec<RangeMap>,
-	water_to_light: Vec<RangeMap>,
-	light_to_temperature: Vec<RangeMap>,
-	temperature_to_humidity: Vec<RangeMap>,
-	humidity_to_location: Vec<RangeMap>,
+	range_map
 }
 
+/// A line of a map, converting the source range to the destination range
 #[derive(Debug, Copy, Clone)]
-struct RangeMap {
+struct MapEntry {
 	dst_range_start: i64,
 	src_range_start: i64,
 	len: i64,
 }
 
-impl FromStr for RangeMap {
+impl FromStr for MapEntry {
 	type Err = anyhow::Error;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		let numbers: Vec<i64> = s.split(" ").map(|n| n.parse::<i64>()).try_collect()?;
-		Ok(RangeMap {
+		if numbers.len() != 3 {
+			bail!("expected 3 numbers in map line \"{}\"", s);
+		}
+		Ok(MapEntry {
 			dst_range_start: numbers[0],
 			src_range_start: numbers[1],
 			len: numbers[2],
@@ -194,6 +221,11 @@ impl FromStr for RangeMap {
 
 #[cfg(test)]
 mod tests {
+	use super::{Day, MapEntry};
+	use aoc_lib::{RangeSet, Solution};
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
 	const EXAMPLE_1: &str = "seeds: 79 14 55 13
 
 seed-to-soil map:
@@ -230,11 +262,78 @@ humidity-to-location map:
 ";
 	#[test]
 	fn part_1() {
-		assert_eq!(super::part_1(EXAMPLE_1).unwrap(), 35);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 35);
 	}
 
 	#[test]
 	fn part_2() {
-		assert_eq!(super::part_2(EXAMPLE_1).unwrap(), 46);
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 46);
+	}
+
+	#[test]
+	fn pipeline() {
+		let almanac = Day::parse(EXAMPLE_1).unwrap();
+		assert_eq!(almanac.stages.len(), 7);
+		let seeds = almanac.seeds_for(46);
+		assert_eq!((seeds.min(), seeds.len()), (Some(82), 1));
+		assert!(almanac.seeds_for(35).is_empty());
+
+		let printed = almanac.to_string();
+		assert!(printed.starts_with("seed-to-location map:\n"));
+		// The printed pipeline is an almanac of its own, with the same answers
+		let single_stage = format!("seeds: 79 14 55 13\n\n{}", printed);
+		assert_eq!(Day::solve_part_1(&single_stage).unwrap(), 35);
+		assert_eq!(Day::solve_part_2(&single_stage).unwrap(), 46);
+	}
+
+	#[test]
+	fn any_number_of_stages() {
+		let almanac = Day::parse("seeds: 1 5\n\na-to-b map:\n10 1 2\n\nb-to-c map:\n").unwrap();
+		assert_eq!(almanac.stages.len(), 2);
+		assert_eq!(Day::part_1(&almanac).unwrap(), 5);
+		assert_eq!(almanac.to_string(), "a-to-c map:\n10 1 2\n");
+
+		assert!(Day::parse("seeds: 1\n\na-to-b map:\n\nc-to-d map:\n").is_err());
+		assert!(Day::parse("seeds: 1\n\n1 2 3\n").is_err());
+	}
+
+	#[test]
+	fn seed_range_covering_a_map_range() {
+		let map = [MapEntry {
+			dst_range_start: 100,
+			src_range_start: 10,
+			len: 10,
+		}];
+		assert_eq!(
+			super::range_map(&map)
+				.map_set(&RangeSet::from(0..30))
+				.ranges(),
+			[0..10, 20..30, 100..110]
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn range_map_matches_convert(
+			entries in vec((0i64..100, 0i64..100, 1i64..30), 0..6),
+			seeds in vec((0i64..100, 0i64..30), 1..4),
+		) {
+			let map: Vec<MapEntry> = entries
+				.into_iter()
+				.map(|(dst_range_start, src_range_start, len)| MapEntry {
+					dst_range_start,
+					src_range_start,
+					len,
+				})
+				.collect();
+			let seeds: RangeSet = seeds.into_iter().map(|(start, len)| start..start + len).collect();
+			let points: Vec<i64> = seeds.ranges().iter().cloned().flatten().collect();
+
+			let expected: RangeSet = super::convert(&points, &map)
+				.into_iter()
+				.map(|point| point..point + 1)
+				.collect();
+			prop_assert_eq!(super::range_map(&map).map_set(&seeds), expected);
+		}
 	}
 }
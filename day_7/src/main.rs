@@ -1,147 +1,154 @@
 use anyhow::bail;
+use aoc_lib::Solution;
 use itertools::Itertools;
 use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_7/input.txt")?;
-	println!("Part 1: {}", part_1(&input)?);
-	println!("Part 2: {}", part_2(&input)?);
-	Ok(())
-}
+aoc_lib::main!(Day);
 
-fn part_1(input: &str) -> anyhow::Result<i64> {
-	let mut hands = Vec::new();
-	for line in input.lines() {
-		let (cards_str, bid) = line.split(' ').next_tuple().unwrap();
-		let mut cards = [0; 5];
-		for idx in 0..5 {
-			let c = cards_str.as_bytes()[idx];
-			if c.is_ascii_digit() {
-				cards[idx] = c - b'0';
-			} else {
-				cards[idx] = match c {
-					b'T' => 10,
-					b'J' => 11,
-					b'Q' => 12,
-					b'K' => 13,
-					b'A' => 14,
-					_ => bail!("invalid card {}", c as char),
-				};
-			}
-		}
+struct Day;
 
-		let bid = bid.parse::<i64>()?;
+impl Solution for Day {
+	type Parsed<'a> = Vec<(&'a str, i64)>;
+	type Answer = i64;
 
-		let mut card_counts = HashMap::new();
-		cards
-			.iter()
-			.for_each(|c| *card_counts.entry(c).or_default() += 1);
-		let mut card_counts: Vec<u8> = card_counts.into_values().collect();
-		card_counts.sort_unstable_by_key(|c| Reverse(*c));
-
-		let hand_kind = match card_counts.as_slice() {
-			[5] => HandKind::FiveOfAKind,
-			[4, 1] => HandKind::FourOfAKind,
-			[3, 2] => HandKind::FullHouse,
-			[3, 1, 1] => HandKind::ThreeOfAKind,
-			[2, 2, 1] => HandKind::TwoPair,
-			[2, 1, 1, 1] => HandKind::OnePair,
-			[1, 1, 1, 1, 1] => HandKind::HighCard,
-			_ => bail!("invalid card pattern {card_counts:?} for {cards_str}"),
-		};
-
-		hands.push(Hand {
-			cards,
-			kind: hand_kind,
-			bid,
-		});
+	/// Splits hands into cards and bids. Cards are valued by the parts, as J is a jack in part 1
+	/// and a joker in part 2
+	fn parse(input: &str) -> anyhow::Result<Vec<(&str, i64)>> {
+		input
+			.lines()
+			.map(|line| {
+				let (cards_str, bid) = line.split(' ').next_tuple().unwrap();
+				Ok((cards_str, bid.parse::<i64>()?))
+			})
+			.try_collect()
 	}
 
-	hands.sort();
-	let total_winnings = hands
-		.iter()
-		.enumerate()
-		.map(|(idx, hand)| hand.bid * (idx as i64 + 1))
-		.sum();
-
-	Ok(total_winnings)
-}
-
-fn part_2(input: &str) -> anyhow::Result<i64> {
-	let mut hands = Vec::new();
-	for line in input.lines() {
-		let (cards_str, bid) = line.split(' ').next_tuple().unwrap();
-		let mut cards = [0; 5];
-		for idx in 0..5 {
-			let c = cards_str.as_bytes()[idx];
-			if c.is_ascii_digit() {
-				cards[idx] = c - b'0';
-			} else {
-				cards[idx] = match c {
-					b'T' => 10,
-					b'J' => 1,
-					b'Q' => 12,
-					b'K' => 13,
-					b'A' => 14,
-					_ => bail!("invalid card {}", c as char),
-				};
+	fn part_1(lines: &Vec<(&str, i64)>) -> anyhow::Result<i64> {
+		let mut hands = Vec::new();
+		for &(cards_str, bid) in lines {
+			let mut cards = [0; 5];
+			for (card, &c) in cards.iter_mut().zip(cards_str.as_bytes()) {
+				if c.is_ascii_digit() {
+					*card = c - b'0';
+				} else {
+					*card = match c {
+						b'T' => 10,
+						b'J' => 11,
+						b'Q' => 12,
+						b'K' => 13,
+						b'A' => 14,
+						_ => bail!("invalid card {}", c as char),
+					};
+				}
 			}
-		}
 
-		let bid = bid.parse::<i64>()?;
+			let mut card_counts = HashMap::new();
+			cards
+				.iter()
+				.for_each(|c| *card_counts.entry(c).or_default() += 1);
+			let mut card_counts: Vec<u8> = card_counts.into_values().collect();
+			card_counts.sort_unstable_by_key(|c| Reverse(*c));
+
+			let hand_kind = match card_counts.as_slice() {
+				[5] => HandKind::FiveOfAKind,
+				[4, 1] => HandKind::FourOfAKind,
+				[3, 2] => HandKind::FullHouse,
+				[3, 1, 1] => HandKind::ThreeOfAKind,
+				[2, 2, 1] => HandKind::TwoPair,
+				[2, 1, 1, 1] => HandKind::OnePair,
+				[1, 1, 1, 1, 1] => HandKind::HighCard,
+				_ => bail!("invalid card pattern {card_counts:?} for {cards_str}"),
+			};
+
+			hands.push(Hand {
+				cards,
+				kind: hand_kind,
+				bid,
+			});
+		}
 
-		let mut card_counts = HashMap::new();
-		cards
+		hands.sort();
+		let total_winnings = hands
 			.iter()
-			.for_each(|c| *card_counts.entry(c).or_default() += 1);
-		let jocker_count = card_counts.get(&1).copied().unwrap_or_default();
-		let mut card_counts: Vec<u8> = card_counts.into_values().collect();
-		card_counts.sort_unstable_by_key(|c| Reverse(*c));
-
-		let hand_kind = match card_counts.as_slice() {
-			[5] => HandKind::FiveOfAKind,
-
-			[4, 1] if jocker_count == 1 => HandKind::FiveOfAKind,
-			[4, 1] if jocker_count == 4 => HandKind::FiveOfAKind,
-			[4, 1] => HandKind::FourOfAKind,
-
-			[3, 2] if jocker_count == 2 => HandKind::FiveOfAKind,
-			[3, 2] if jocker_count == 3 => HandKind::FiveOfAKind,
-			[3, 2] => HandKind::FullHouse,
-
-			[3, 1, 1] if jocker_count == 1 => HandKind::FourOfAKind,
-			[3, 1, 1] if jocker_count == 3 => HandKind::FourOfAKind,
-			[3, 1, 1] => HandKind::ThreeOfAKind,
-
-			[2, 2, 1] if jocker_count == 1 => HandKind::FullHouse,
-			[2, 2, 1] if jocker_count == 2 => HandKind::FourOfAKind,
-			[2, 2, 1] => HandKind::TwoPair,
-
-			[2, 1, 1, 1] if jocker_count == 1 => HandKind::ThreeOfAKind,
-			[2, 1, 1, 1] if jocker_count == 2 => HandKind::ThreeOfAKind,
-			[2, 1, 1, 1] => HandKind::OnePair,
-
-			[1, 1, 1, 1, 1] if jocker_count == 1 => HandKind::OnePair,
-			[1, 1, 1, 1, 1] => HandKind::HighCard,
-			_ => bail!("invalid card pattern {card_counts:?} for {cards_str}"),
-		};
-
-		hands.push(Hand {
-			cards,
-			kind: hand_kind,
-			bid,
-		});
+			.enumerate()
+			.map(|(idx, hand)| hand.bid * (idx as i64 + 1))
+			.sum();
+
+		Ok(total_winnings)
 	}
 
-	hands.sort_unstable();
-	let total_winnings = hands
-		.iter()
-		.enumerate()
-		.map(|(idx, hand)| hand.bid * (idx as i64 + 1))
-		.sum();
+	fn part_2(lines: &Vec<(&str, i64)>) -> anyhow::Result<i64> {
+		let mut hands = Vec::new();
+		for &(cards_str, bid) in lines {
+			let mut cards = [0; 5];
+			for (card, &c) in cards.iter_mut().zip(cards_str.as_bytes()) {
+				if c.is_ascii_digit() {
+					*card = c - b'0';
+				} else {
+					*card = match c {
+						b'T' => 10,
+						b'J' => 1,
+						b'Q' => 12,
+						b'K' => 13,
+						b'A' => 14,
+						_ => bail!("invalid card {}", c as char),
+					};
+				}
+			}
+
+			let mut card_counts = HashMap::new();
+			cards
+				.iter()
+				.for_each(|c| *card_counts.entry(c).or_default() += 1);
+			let jocker_count = card_counts.get(&1).copied().unwrap_or_default();
+			let mut card_counts: Vec<u8> = card_counts.into_values().collect();
+			card_counts.sort_unstable_by_key(|c| Reverse(*c));
+
+			let hand_kind = match card_counts.as_slice() {
+				[5] => HandKind::FiveOfAKind,
+
+				[4, 1] if jocker_count == 1 => HandKind::FiveOfAKind,
+				[4, 1] if jocker_count == 4 => HandKind::FiveOfAKind,
+				[4, 1] => HandKind::FourOfAKind,
+
+				[3, 2] if jocker_count == 2 => HandKind::FiveOfAKind,
+				[3, 2] if jocker_count == 3 => HandKind::FiveOfAKind,
+				[3, 2] => HandKind::FullHouse,
+
+				[3, 1, 1] if jocker_count == 1 => HandKind::FourOfAKind,
+				[3, 1, 1] if jocker_count == 3 => HandKind::FourOfAKind,
+				[3, 1, 1] => HandKind::ThreeOfAKind,
+
+				[2, 2, 1] if jocker_count == 1 => HandKind::FullHouse,
+				[2, 2, 1] if jocker_count == 2 => HandKind::FourOfAKind,
+				[2, 2, 1] => HandKind::TwoPair,
+
+				[2, 1, 1, 1] if jocker_count == 1 => HandKind::ThreeOfAKind,
+				[2, 1, 1, 1] if jocker_count == 2 => HandKind::ThreeOfAKind,
+				[2, 1, 1, 1] => HandKind::OnePair,
+
+				[1, 1, 1, 1, 1] if jocker_count == 1 => HandKind::OnePair,
+				[1, 1, 1, 1, 1] => HandKind::HighCard,
+				_ => bail!("invalid card pattern {card_counts:?} for {cards_str}"),
+			};
+
+			hands.push(Hand {
+				cards,
+				kind: hand_kind,
+				bid,
+			});
+		}
+
+		hands.sort_unstable();
+		let total_winnings = hands
+			.iter()
+			.enumerate()
+			.map(|(idx, hand)| hand.bid * (idx as i64 + 1))
+			.sum();
 
-	Ok(total_winnings)
+		Ok(total_winnings)
+	}
 }
 
 #[derive(Debug, Eq)]
@@ -186,6 +193,9 @@ enum HandKind {
 
 #[cfg(test)]
 mod tests {
+	use super::Day;
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = "32T3K 765
 T55J5 684
 KK677 28
@@ -194,11 +204,11 @@ QQQJA 483
 ";
 	#[test]
 	fn part_1() {
-		assert_eq!(super::part_1(EXAMPLE_1).unwrap(), 6440);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 6440);
 	}
 
 	#[test]
 	fn part_2() {
-		assert_eq!(super::part_2(EXAMPLE_1).unwrap(), 5905);
+		assert_eq!(Day::solve_part_2(EXAMPLE_1).unwrap(), 5905);
 	}
 }
@@ -1,96 +1,190 @@
-use anyhow::bail;
+use anyhow::{bail, Context};
+use aoc_lib::math::{self, CrtError};
+use aoc_lib::Solution;
 use itertools::Itertools;
 use std::collections::HashMap;
 
-fn main() -> anyhow::Result<()> {
-	let input = std::fs::read_to_string("day_8/input.txt")?;
-	println!("Part 1: {}", part_1(&input)?);
-	println!("Part 2: {}", part_2(&input)?);
-	Ok(())
-}
+aoc_lib::main!(Day);
 
-fn part_1(input: &str) -> anyhow::Result<i64> {
-	let (steps, nodes) = parse_input(input)?;
+struct Day;
 
-	let mut current = "AAA";
-	let mut step_count = 0;
-	for step in steps.iter().copied().cycle() {
-		if current == "ZZZ" {
-			break;
-		}
-		current = nodes[current][step];
-		step_count += 1;
-	}
+impl Solution for Day {
+	type Parsed<'a> = (Vec<usize>, Nodes<'a>);
+	type Answer = i64;
 
-	Ok(step_count)
-}
+	fn parse(input: &str) -> anyhow::Result<(Vec<usize>, Nodes<'_>)> {
+		let mut lines = input.lines();
+		let steps: Vec<usize> = lines
+			.next()
+			.unwrap()
+			.bytes()
+			.map(|b| match b {
+				b'L' => Ok(0),
+				b'R' => Ok(1),
+				_ => bail!("invalid step {}", b as char),
+			})
+			.try_collect()?;
+
+		let _ = lines.next().unwrap();
 
-fn part_2(input: &str) -> anyhow::Result<i64> {
-	let (steps, nodes) = parse_input(input)?;
-
-	let mut currents: Vec<&str> = nodes
-		.keys()
-		.filter(|x| x.ends_with("A"))
-		.map(|x| *x)
-		.collect();
-	let mut currents_step_counts = Vec::new();
-	let currents_len = currents.len();
-	for (idx, step) in steps.iter().copied().cycle().enumerate() {
-		if currents_step_counts.len() == currents_len {
-			break;
+		let mut nodes = HashMap::<&str, [&str; 2]>::new();
+		for node in lines {
+			let (node, next_nodes) = node.split(" = (").next_tuple().unwrap();
+			let (next_node_a, next_node_b) = next_nodes.split(", ").next_tuple().unwrap();
+			nodes.insert(node, [next_node_a, &next_node_b[0..3]]);
 		}
-		currents.retain_mut(|current| {
-			*current = nodes[current][step];
-			if current.ends_with("Z") {
-				currents_step_counts.push(idx as i64 + 1);
-				return false;
+
+		Ok((steps, nodes))
+	}
+
+	fn part_1((steps, nodes): &(Vec<usize>, Nodes<'_>)) -> anyhow::Result<i64> {
+		let mut current = "AAA";
+		let mut step_count = 0;
+		for step in steps.iter().copied().cycle() {
+			if current == "ZZZ" {
+				break;
 			}
-			true
-		});
+			current = nodes[current][step];
+			step_count += 1;
+		}
+
+		Ok(step_count)
 	}
 
-	Ok(currents_step_counts.into_iter().fold(1, lcm))
+	fn part_2((steps, nodes): &(Vec<usize>, Nodes<'_>)) -> anyhow::Result<i64> {
+		let ghosts: Vec<Ghost> = nodes
+			.keys()
+			.filter(|node| node.ends_with('A'))
+			.sorted()
+			.map(|start| Ghost::new(start, steps, nodes))
+			.collect();
+		if ghosts.is_empty() {
+			bail!("no nodes ending with A");
+		}
+
+		// Before all ghosts are in their cycles, the one with the longest tail can only be on a Z
+		// node at the hits of its tail
+		let longest = ghosts.iter().max_by_key(|ghost| ghost.tail).unwrap();
+		if let Some(step) = longest
+			.tail_hits
+			.iter()
+			.find(|&&step| ghosts.iter().all(|ghost| ghost.is_on_z(step)))
+		{
+			return Ok(*step);
+		}
+
+		// After that, each combination of cycle hits is a system of congruences
+		let mut congruences = vec![(0, 1)];
+		for ghost in &ghosts {
+			congruences = congruences
+				.into_iter()
+				.cartesian_product(&ghost.cycle_hits)
+				.filter_map(|(congruence, &hit)| {
+					match math::crt([congruence, (hit, ghost.cycle)]) {
+						Err(CrtError::NoSolution) => None,
+						solution => Some(solution),
+					}
+				})
+				.try_collect()?;
+			congruences.sort();
+			congruences.dedup();
+		}
+
+		congruences
+			.into_iter()
+			.map(|(step, period)| {
+				if step >= longest.tail {
+					step
+				} else {
+					step + (longest.tail - step + period - 1) / period * period
+				}
+			})
+			.min()
+			.context("the ghosts are never all on Z nodes at once")
+	}
 }
 
-fn lcm(a: i64, b: i64) -> i64 {
-	(a * b).abs() / gcd(a, b)
+/// The steps at which a ghost is on a Z node: `tail_hits` before it enters its cycle after `tail`
+/// steps, then `cycle_hits` and every `cycle` steps after those
+#[derive(Debug, PartialEq, Eq)]
+struct Ghost {
+	tail: i64,
+	cycle: i64,
+	tail_hits: Vec<i64>,
+	cycle_hits: Vec<i64>,
 }
 
-fn gcd(a: i64, b: i64) -> i64 {
-	if b == 0 {
-		a
-	} else {
-		gcd(b, a.rem_euclid(b))
+impl Ghost {
+	fn new(start: &str, steps: &[usize], nodes: &Nodes<'_>) -> Ghost {
+		// The ghost's state is its node and its position in the instructions
+		let next = |(node, idx): (&str, usize)| (nodes[node][steps[idx]], (idx + 1) % steps.len());
+		let (tail, cycle) = find_cycle((start, 0), next);
+
+		let (mut tail_hits, mut cycle_hits) = (Vec::new(), Vec::new());
+		let mut state = (start, 0);
+		for step in 0..tail + cycle {
+			if state.0.ends_with('Z') {
+				if step < tail {
+					tail_hits.push(step as i64);
+				} else {
+					cycle_hits.push(step as i64);
+				}
+			}
+			state = next(state);
+		}
+
+		Ghost {
+			tail: tail as i64,
+			cycle: cycle as i64,
+			tail_hits,
+			cycle_hits,
+		}
+	}
+
+	fn is_on_z(&self, step: i64) -> bool {
+		if step < self.tail {
+			self.tail_hits.contains(&step)
+		} else {
+			let in_cycle = self.tail + (step - self.tail) % self.cycle;
+			self.cycle_hits.contains(&in_cycle)
+		}
 	}
 }
 
-fn parse_input(input: &str) -> anyhow::Result<(Vec<usize>, HashMap<&str, [&str; 2]>)> {
-	let mut lines = input.lines();
-	let steps: Vec<usize> = lines
-		.next()
-		.unwrap()
-		.bytes()
-		.map(|b| match b {
-			b'L' => Ok(0),
-			b'R' => Ok(1),
-			_ => bail!("invalid step {}", b as char),
-		})
-		.try_collect()?;
-
-	let _ = lines.next().unwrap();
-
-	let mut nodes = HashMap::<&str, [&str; 2]>::new();
-	for node in lines {
-		let (node, next_nodes) = node.split(" = (").next_tuple().unwrap();
-		let (next_node_a, next_node_b) = next_nodes.split(", ").next_tuple().unwrap();
-		nodes.insert(node, [next_node_a, &next_node_b[0..3]]);
+/// Brent's algorithm, returning the number of steps before the cycle starts and its length
+fn find_cycle<S: Copy + Eq>(start: S, next: impl Fn(S) -> S) -> (usize, usize) {
+	let (mut power, mut cycle) = (1, 1);
+	let (mut tortoise, mut hare) = (start, next(start));
+	while tortoise != hare {
+		if power == cycle {
+			tortoise = hare;
+			power *= 2;
+			cycle = 0;
+		}
+		hare = next(hare);
+		cycle += 1;
 	}
 
-	Ok((steps, nodes))
+	let (mut tortoise, mut hare) = (start, start);
+	for _ in 0..cycle {
+		hare = next(hare);
+	}
+	let mut tail = 0;
+	while tortoise != hare {
+		tortoise = next(tortoise);
+		hare = next(hare);
+		tail += 1;
+	}
+	(tail, cycle)
 }
 
+type Nodes<'a> = HashMap<&'a str, [&'a str; 2]>;
+
 #[cfg(test)]
 mod tests {
+	use super::{Day, Ghost};
+	use aoc_lib::Solution;
+
 	const EXAMPLE_1: &str = "RL
 
 AAA = (BBB, CCC)
@@ -104,7 +198,7 @@ ZZZ = (ZZZ, ZZZ)
 
 	#[test]
 	fn part_1_example_1() {
-		assert_eq!(super::part_1(EXAMPLE_1).unwrap(), 2);
+		assert_eq!(Day::solve_part_1(EXAMPLE_1).unwrap(), 2);
 	}
 
 	const EXAMPLE_2: &str = "LLR
@@ -116,7 +210,7 @@ ZZZ = (ZZZ, ZZZ)
 
 	#[test]
 	fn part_1_example_2() {
-		assert_eq!(super::part_1(EXAMPLE_2).unwrap(), 6);
+		assert_eq!(Day::solve_part_1(EXAMPLE_2).unwrap(), 6);
 	}
 
 	const EXAMPLE_3: &str = "LR
@@ -133,6 +227,45 @@ XXX = (XXX, XXX)
 
 	#[test]
 	fn part_2_example_3() {
-		assert_eq!(super::part_2(EXAMPLE_3).unwrap(), 6);
+		assert_eq!(Day::solve_part_2(EXAMPLE_3).unwrap(), 6);
+	}
+
+	#[test]
+	fn ghost_cycles() {
+		let (steps, nodes) = Day::parse(EXAMPLE_3).unwrap();
+		assert_eq!(
+			Ghost::new("22A", &steps, &nodes),
+			Ghost {
+				tail: 1,
+				cycle: 6,
+				tail_hits: vec![],
+				cycle_hits: vec![3, 6],
+			}
+		);
+	}
+
+	// The first Z nodes are 2 and 1 steps away, but the ghosts only meet at step 5
+	const TAILS: &str = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)
+";
+
+	#[test]
+	fn part_2_tails() {
+		assert_eq!(Day::solve_part_2(TAILS).unwrap(), 5);
+
+		// A ghost only passing a Z node once
+		let once = "33A = (33Z, 33Z)\n33Z = (33B, 33B)\n33B = (33B, 33B)\n";
+		let with_22 = TAILS.replace("11A", "11X") + once;
+		assert_eq!(Day::solve_part_2(&with_22).unwrap(), 1);
+		let with_11 = TAILS.replace("22A", "22X") + once;
+		assert!(Day::solve_part_2(&with_11).is_err());
 	}
 }